@@ -12,6 +12,8 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use syn::parse::{Parse, ParseStream};
 
+mod scan;
+
 struct InputArgs {
     year: u32,
     day: u32,
@@ -109,3 +111,26 @@ fn get_session_cookie() -> String {
         - Copying the value of the 'session' cookie"
     );
 }
+
+/// Matches a string against a fixed template and parses the values in it, without allocating.
+/// Returns `Result<(T1, T2, ...), aoc::parse::Error>`.
+///
+/// `{}` is a value of the type at the same position after `=>`, anything that implements
+/// `aoc::parse::Scan` (integers, floats, `char`, `&str`, ...). It extends up to the next literal
+/// text of the pattern, or to the end of the string. `{sep}` with a non-empty separator is a list
+/// of values with `sep` between them, and gives an `aoc::parse::List`, which iterates over them.
+/// `{{` and `}}` are literal braces.
+///
+/// The whole string has to match. Errors name the column and the placeholder that did not parse.
+///
+/// ```ignore
+/// let (px, py, vx, vy) = scan!(line, "p={},{} v={},{}" => u32, u32, i32, i32)?;
+/// let (valve, rate, tunnels) = scan!(line, "Valve {} has flow rate={}; tunnels lead to valves {, }" => &str, u32, &str)?;
+/// ```
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(input as scan::ScanArgs);
+    scan::expand(args)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,187 @@
+//! The implementation of `scan!`, see the documentation on the macro in `lib.rs`.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, LitStr, Token, Type};
+
+pub struct ScanArgs {
+    input: Expr,
+    pattern: LitStr,
+    types: Punctuated<Type, Token![,]>,
+}
+
+impl Parse for ScanArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pattern = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let types = Punctuated::parse_terminated(input)?;
+
+        Ok(ScanArgs {
+            input: expr,
+            pattern,
+            types,
+        })
+    }
+}
+
+/// A piece of the pattern.
+enum Piece {
+    Literal(String),
+    /// `{}`
+    Value,
+    /// `{sep}`, a list of values with `sep` between them
+    List(String),
+}
+
+/// Splits the pattern into literals and placeholders, with `{{` and `}}` as escaped braces.
+fn pieces(pattern: &LitStr) -> syn::Result<Vec<Piece>> {
+    let error = |message: &str| syn::Error::new(pattern.span(), message);
+
+    let mut pieces = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern
+        .value()
+        .chars()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut separator = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => separator.push(c),
+                        None => return Err(error("unclosed `{` in pattern")),
+                    }
+                }
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                } else if pieces
+                    .last()
+                    .is_some_and(|p| !matches!(p, Piece::Literal(_)))
+                {
+                    return Err(error(
+                        "placeholders need some literal text between them to know where to split",
+                    ));
+                }
+                pieces.push(if separator.is_empty() {
+                    Piece::Value
+                } else {
+                    Piece::List(separator)
+                });
+            }
+            '}' => {
+                return Err(error(
+                    "unmatched `}` in pattern, use `}}` for a literal brace",
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+pub fn expand(args: ScanArgs) -> syn::Result<TokenStream> {
+    let pieces = pieces(&args.pattern)?;
+    let placeholders = pieces
+        .iter()
+        .filter(|p| !matches!(p, Piece::Literal(_)))
+        .count();
+    if placeholders != args.types.len() {
+        return Err(syn::Error::new(
+            args.pattern.span(),
+            format!(
+                "the pattern has {placeholders} placeholders but {} types are given",
+                args.types.len()
+            ),
+        ));
+    }
+
+    let mut steps = vec![];
+    let mut values = vec![];
+    let mut types = args.types.iter();
+    for (i, piece) in pieces.iter().enumerate() {
+        let step = match piece {
+            Piece::Literal(literal) => {
+                let expected = format!("{literal:?}");
+                quote! {
+                    if !__input[__pos..].starts_with(#literal) {
+                        break '__scan Err(::aoc::parse::Error::at(__input, __pos, #expected));
+                    }
+                    __pos += #literal.len();
+                }
+            }
+            Piece::Value | Piece::List(_) => {
+                let n = values.len();
+                let value = format_ident!("__value{}", n);
+                let ty = types.next().unwrap();
+
+                // A placeholder extends up to the next literal, or to the end of the input
+                let end = match pieces.get(i + 1) {
+                    Some(Piece::Literal(next)) => {
+                        let expected = format!("{next:?}");
+                        quote! {
+                            match __input[__pos..].find(#next) {
+                                Some(len) => __pos + len,
+                                None => break '__scan Err(::aoc::parse::Error::at(__input, __input.len(), #expected)),
+                            }
+                        }
+                    }
+                    _ => quote! { __input.len() },
+                };
+
+                let (parse, expected) = match piece {
+                    Piece::List(separator) => (
+                        quote! { ::aoc::parse::List::<#ty>::new(__field, #separator) },
+                        format!("list of {} for placeholder {}", quote!(#ty), n + 1),
+                    ),
+                    _ => (
+                        quote! { <#ty as ::aoc::parse::Scan>::scan(__field) },
+                        format!("{} for placeholder {}", quote!(#ty), n + 1),
+                    ),
+                };
+
+                values.push(value.clone());
+                quote! {
+                    let __end = #end;
+                    let __field = &__input[__pos..__end];
+                    let #value = match #parse {
+                        Some(value) => value,
+                        None => break '__scan Err(::aoc::parse::Error::at(__input, __pos, #expected)),
+                    };
+                    __pos = __end;
+                }
+            }
+        };
+        steps.push(step);
+    }
+
+    let input = &args.input;
+    Ok(quote! {
+        '__scan: {
+            let __input: &str = #input;
+            let mut __pos = 0usize;
+            #(#steps)*
+            if __pos != __input.len() {
+                break '__scan Err(::aoc::parse::Error::at(__input, __pos, "end of input"));
+            }
+            Ok((#(#values,)*))
+        }
+    })
+}
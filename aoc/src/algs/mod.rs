@@ -1,3 +1,5 @@
 mod binary_search;
+mod subset_dp;
 
 pub use binary_search::binary_search;
+pub use subset_dp::{best_per_visited_set, shortest_path_visiting_all, shortest_tour, SubsetTable};
@@ -0,0 +1,293 @@
+//! Dynamic programming over subsets of nodes.
+//!
+//! These helpers cover the common "visit some of these nodes" puzzles: travelling salesman over a
+//! small distance matrix, and "what is the best score for each set of visited nodes" searches whose
+//! results are then combined, for example by two agents that must visit disjoint nodes.
+
+use std::collections::HashMap;
+
+use num_traits::Num;
+
+use crate::bitset::BitSet;
+
+/// Held-Karp travelling salesman: the cheapest path that starts at `start` and visits every node
+/// exactly once. The path does not return to `start`.
+///
+/// `dist[i][j]` is the cost of going from node `i` to node `j`. Runs in O(2^n * n^2) time, so it is
+/// only useful for up to ~20 nodes.
+///
+/// Returns the total cost and the order in which the nodes are visited.
+///
+/// ```
+/// use aoc::algs::shortest_path_visiting_all;
+///
+/// let dist = vec![
+///     vec![0, 2, 9, 10],
+///     vec![1, 0, 6, 4],
+///     vec![15, 7, 0, 8],
+///     vec![6, 3, 12, 0],
+/// ];
+///
+/// assert_eq!(shortest_path_visiting_all(&dist, 0), Some((16, vec![0, 1, 2, 3])));
+/// ```
+pub fn shortest_path_visiting_all<T>(dist: &[Vec<T>], start: usize) -> Option<(T, Vec<usize>)>
+where
+    T: Num + Ord + Copy,
+{
+    held_karp(dist, start, false)
+}
+
+/// Held-Karp travelling salesman: the cheapest tour that starts at `start`, visits every node exactly
+/// once and then returns to `start`.
+///
+/// See [`shortest_path_visiting_all`] for details. The returned order starts at `start` and does not
+/// repeat it at the end.
+///
+/// ```
+/// use aoc::algs::shortest_tour;
+///
+/// let dist = vec![
+///     vec![0, 2, 9, 10],
+///     vec![1, 0, 6, 4],
+///     vec![15, 7, 0, 8],
+///     vec![6, 3, 12, 0],
+/// ];
+///
+/// assert_eq!(shortest_tour(&dist, 0), Some((21, vec![0, 2, 3, 1])));
+/// ```
+pub fn shortest_tour<T>(dist: &[Vec<T>], start: usize) -> Option<(T, Vec<usize>)>
+where
+    T: Num + Ord + Copy,
+{
+    held_karp(dist, start, true)
+}
+
+fn held_karp<T>(dist: &[Vec<T>], start: usize, cycle: bool) -> Option<(T, Vec<usize>)>
+where
+    T: Num + Ord + Copy,
+{
+    let n = dist.len();
+    if start >= n {
+        return None;
+    }
+    assert!(n <= 32, "held_karp is limited to 32 nodes");
+
+    // best[mask * n + last] is the cheapest path from `start` through exactly `mask` ending at `last`
+    let size = 1usize << n;
+    let mut best: Vec<Option<T>> = vec![None; size * n];
+    let mut prev = vec![usize::MAX; size * n];
+    best[(1 << start) * n + start] = Some(T::zero());
+
+    for mask in 0..size {
+        if mask & (1 << start) == 0 {
+            continue;
+        }
+        for last in 0..n {
+            let Some(cost) = best[mask * n + last] else {
+                continue;
+            };
+            for next in 0..n {
+                if mask & (1 << next) != 0 {
+                    continue;
+                }
+                let next_mask = mask | (1 << next);
+                let next_cost = cost + dist[last][next];
+                let slot = &mut best[next_mask * n + next];
+                if slot.is_none_or(|c| next_cost < c) {
+                    *slot = Some(next_cost);
+                    prev[next_mask * n + next] = last;
+                }
+            }
+        }
+    }
+
+    let full = size - 1;
+    let (cost, mut last) = (0..n)
+        .filter_map(|last| {
+            let cost = best[full * n + last]?;
+            Some((
+                if cycle {
+                    cost + dist[last][start]
+                } else {
+                    cost
+                },
+                last,
+            ))
+        })
+        .min()?;
+
+    // Walk the predecessor table backwards to recover the order
+    let mut order = Vec::with_capacity(n);
+    let mut mask = full;
+    while last != usize::MAX {
+        order.push(last);
+        let p = prev[mask * n + last];
+        mask &= !(1 << last);
+        last = p;
+    }
+    order.reverse();
+
+    Some((cost, order))
+}
+
+/// `SubsetTable` maps each set of visited nodes to the best score found for it.
+#[derive(Debug, Clone)]
+pub struct SubsetTable<B, T> {
+    best: HashMap<B, T>,
+}
+
+impl<B: BitSet, T: Ord + Copy> SubsetTable<B, T> {
+    pub fn new() -> Self {
+        Self {
+            best: HashMap::new(),
+        }
+    }
+
+    /// `record` stores `score` for `set` if it beats the current best. Returns true if it did.
+    pub fn record(&mut self, set: B, score: T) -> bool {
+        match self.best.get_mut(&set) {
+            Some(best) if *best >= score => false,
+            Some(best) => {
+                *best = score;
+                true
+            }
+            None => {
+                self.best.insert(set, score);
+                true
+            }
+        }
+    }
+
+    /// `get` returns the best score recorded for exactly `set`.
+    pub fn get(&self, set: &B) -> Option<T> {
+        self.best.get(set).copied()
+    }
+
+    /// `max` returns the best score over all sets.
+    pub fn max(&self) -> Option<T> {
+        self.best.values().copied().max()
+    }
+
+    /// `len` returns the number of sets in the table.
+    pub fn len(&self) -> usize {
+        self.best.len()
+    }
+
+    /// `is_empty` returns true if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.best.is_empty()
+    }
+
+    /// Iterates over every `(set, best score)` pair in an arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (B, T)> + '_ {
+        self.best.iter().map(|(&set, &score)| (set, score))
+    }
+
+    /// `best_disjoint_pair` returns the largest `a + b` where `a` and `b` are the scores of two
+    /// disjoint sets in the table.
+    ///
+    /// This is the classic way to split work between two agents: compute the best score for every
+    /// set one agent could visit alone, then pair up sets that do not overlap. The empty set is
+    /// allowed, so a single agent doing everything is also considered.
+    ///
+    /// ```
+    /// use aoc::algs::SubsetTable;
+    /// use aoc::bitset::BitSet64;
+    ///
+    /// let mut table = SubsetTable::new();
+    /// table.record(BitSet64::from_bits(0b011), 10);
+    /// table.record(BitSet64::from_bits(0b110), 12);
+    /// table.record(BitSet64::from_bits(0b100), 5);
+    ///
+    /// // {0, 1} and {2} are disjoint, {0, 1} and {1, 2} are not
+    /// assert_eq!(table.best_disjoint_pair(), Some(15));
+    /// ```
+    pub fn best_disjoint_pair(&self) -> Option<T>
+    where
+        T: Num,
+    {
+        let empty = self.get(&B::empty()).unwrap_or_else(T::zero);
+        let mut sorted: Vec<(B, T)> = self.iter().collect();
+        sorted.sort_unstable_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+        let mut best: Option<T> = None;
+        for (i, &(a, score_a)) in sorted.iter().enumerate() {
+            // Pairing with the empty set is always allowed
+            let mut candidate = score_a + empty;
+
+            // Both lists are sorted, so the first disjoint partner is the best one for `a`
+            for &(b, score_b) in &sorted[i + 1..] {
+                if let Some(best) = best {
+                    if score_a + score_b <= best {
+                        break;
+                    }
+                }
+                if a.is_disjoint(&b) {
+                    candidate = candidate.max(score_a + score_b);
+                    break;
+                }
+            }
+
+            best = Some(best.map_or(candidate, |best| best.max(candidate)));
+        }
+
+        best
+    }
+}
+
+impl<B: BitSet, T: Ord + Copy> Default for SubsetTable<B, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `best_per_visited_set` explores every way of extending a walk through a set of nodes and records
+/// the best accumulated score for each set of visited nodes.
+///
+/// `expand(state, visited)` returns the moves available from `state`, each as a tuple of the next
+/// state, the node it visits (which must not already be in `visited`) and the score gained. The
+/// search is exhaustive, so `expand` should only return moves that are still worthwhile (e.g. when
+/// there is time left).
+///
+/// ```
+/// use aoc::algs::best_per_visited_set;
+/// use aoc::bitset::{BitSet, BitSet64};
+///
+/// // Open valves with flow `rates`, where each move takes `dist` minutes plus one to open.
+/// let rates = [0, 13, 2, 20];
+/// let dist = [[0, 1, 2, 1], [1, 0, 1, 2], [2, 1, 0, 1], [1, 2, 1, 0]];
+///
+/// let table = best_per_visited_set((0usize, 5u32), |&(at, time), visited: BitSet64| {
+///     (0..rates.len())
+///         .filter(move |&next| rates[next] > 0 && !visited.contains(next))
+///         .filter_map(move |next| {
+///             let left = time.checked_sub(dist[at][next] + 1)?;
+///             Some(((next, left), next, rates[next] * left))
+///         })
+/// });
+///
+/// // Open valve 3 with 3 minutes left, then valve 2 with 1 minute left
+/// assert_eq!(table.max(), Some(62));
+/// assert_eq!(table.get(&BitSet64::singleton(1)), Some(39));
+/// ```
+pub fn best_per_visited_set<B, S, T, F, I>(start: S, mut expand: F) -> SubsetTable<B, T>
+where
+    B: BitSet,
+    T: Num + Ord + Copy,
+    F: FnMut(&S, B) -> I,
+    I: IntoIterator<Item = (S, usize, T)>,
+{
+    let mut table = SubsetTable::new();
+    let mut stack = vec![(start, B::empty(), T::zero())];
+
+    while let Some((state, visited, score)) = stack.pop() {
+        table.record(visited, score);
+
+        for (next, node, gain) in expand(&state, visited) {
+            debug_assert!(!visited.contains(node), "node {} visited twice", node);
+            stack.push((next, visited.with(node), score + gain));
+        }
+    }
+
+    table
+}
@@ -0,0 +1,255 @@
+//! Arithmetic for counts that outgrow machine integers. Needs the `bigint` feature.
+//!
+//! [`Checked`] makes an overflow panic with the operands instead of wrapping silently, so it is
+//! obvious when a solution needs [`Counter`] or the [`big_sum`]/[`big_product`] adapters instead.
+//!
+//! [`big_sum`]: crate::IterJunk::big_sum
+//! [`big_product`]: crate::IterJunk::big_product
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+pub use num_bigint::{BigInt, BigUint};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, ToPrimitive, Zero};
+
+/// `Checked` wraps an integer so that overflowing `+`, `-` and `*` panic with both operands in
+/// debug builds. Release builds use the plain operators.
+///
+/// ```
+/// use aoc::bigint::Checked;
+///
+/// let total: Checked<u8> = [100, 50, 70].into_iter().map(Checked).sum();
+/// assert_eq!(total.0, 220);
+/// ```
+///
+/// ```should_panic
+/// use aoc::bigint::Checked;
+///
+/// // Panics with "attempt to add with overflow: 200 + 100 (u8)"
+/// let _ = Checked(200u8) + Checked(100);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! checked_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign:ident, $checked:ident, $checked_method:ident, $verb:literal, $symbol:literal) => {
+        impl<T: $checked + fmt::Display> $trait for Checked<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                #[cfg(debug_assertions)]
+                let result = self.0.$checked_method(&rhs.0).unwrap_or_else(|| {
+                    panic!(
+                        concat!(
+                            "attempt to ",
+                            $verb,
+                            " with overflow: {} ",
+                            $symbol,
+                            " {} ({})"
+                        ),
+                        self.0,
+                        rhs.0,
+                        std::any::type_name::<T>()
+                    )
+                });
+                #[cfg(not(debug_assertions))]
+                let result = self.0.$method(rhs.0);
+                Checked(result)
+            }
+        }
+
+        impl<T: $checked + fmt::Display + Copy> $assign_trait for Checked<T> {
+            fn $assign(&mut self, rhs: Self) {
+                *self = $trait::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+checked_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    CheckedAdd,
+    checked_add,
+    "add",
+    "+"
+);
+checked_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    CheckedSub,
+    checked_sub,
+    "subtract",
+    "-"
+);
+checked_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    CheckedMul,
+    checked_mul,
+    "multiply",
+    "*"
+);
+
+impl<T: CheckedAdd + fmt::Display + Zero> Sum for Checked<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Checked(T::zero()), Add::add)
+    }
+}
+
+impl<T: CheckedMul + fmt::Display + num_traits::One> Product for Checked<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Checked(T::one()), Mul::mul)
+    }
+}
+
+/// `Counter` is an unbounded non-negative count, for numbers of paths, arrangements or timelines.
+///
+/// ```
+/// use aoc::bigint::Counter;
+///
+/// // The number of paths through a 40 x 40 grid is C(80, 40)
+/// let mut row = vec![Counter::from(1u64); 41];
+/// for _ in 0..40 {
+///     for x in 1..row.len() {
+///         let left = row[x - 1].clone();
+///         row[x] += &left;
+///     }
+/// }
+/// assert_eq!(row[40].to_string(), "107507208733336176461620");
+/// assert_eq!(row[40].to_u64(), None);
+/// assert_eq!(row[1].to_u64(), Some(41));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Counter(BigUint);
+
+impl Counter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(&self) -> &BigUint {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> BigUint {
+        self.0
+    }
+
+    /// The count, if it fits into a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+}
+
+impl From<u64> for Counter {
+    fn from(n: u64) -> Self {
+        Self(n.into())
+    }
+}
+
+impl From<usize> for Counter {
+    fn from(n: usize) -> Self {
+        Self(n.into())
+    }
+}
+
+impl From<BigUint> for Counter {
+    fn from(n: BigUint) -> Self {
+        Self(n)
+    }
+}
+
+impl AddAssign<&Counter> for Counter {
+    fn add_assign(&mut self, rhs: &Counter) {
+        self.0 += &rhs.0;
+    }
+}
+
+impl AddAssign<u64> for Counter {
+    fn add_assign(&mut self, rhs: u64) {
+        self.0 += rhs;
+    }
+}
+
+impl Add<&Counter> for Counter {
+    type Output = Counter;
+
+    fn add(mut self, rhs: &Counter) -> Counter {
+        self += rhs;
+        self
+    }
+}
+
+impl Add for Counter {
+    type Output = Counter;
+
+    fn add(self, rhs: Counter) -> Counter {
+        self + &rhs
+    }
+}
+
+impl MulAssign<u64> for Counter {
+    fn mul_assign(&mut self, rhs: u64) {
+        self.0 *= rhs;
+    }
+}
+
+impl Mul<&Counter> for &Counter {
+    type Output = Counter;
+
+    fn mul(self, rhs: &Counter) -> Counter {
+        Counter(&self.0 * &rhs.0)
+    }
+}
+
+impl Sum for Counter {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Counter::new(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Counter> for Counter {
+    fn sum<I: Iterator<Item = &'a Counter>>(iter: I) -> Self {
+        iter.fold(Counter::new(), Add::add)
+    }
+}
+
+impl fmt::Display for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Folds in `i128` as long as nothing overflows, then carries on with a `BigInt`.
+pub(crate) fn promoting_fold<I>(
+    mut iter: I,
+    init: i128,
+    small: fn(i128, i128) -> Option<i128>,
+    big: fn(BigInt, BigInt) -> BigInt,
+) -> BigInt
+where
+    I: Iterator,
+    I::Item: ToPrimitive + Into<BigInt>,
+{
+    let mut acc = init;
+    while let Some(x) = iter.next() {
+        match x.to_i128().and_then(|n| small(acc, n)) {
+            Some(next) => acc = next,
+            None => return iter.fold(big(acc.into(), x.into()), |acc, x| big(acc, x.into())),
+        }
+    }
+    acc.into()
+}
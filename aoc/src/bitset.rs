@@ -0,0 +1,365 @@
+//! Small fixed-size bit sets.
+//!
+//! These are meant to be used as "visited" masks in subset dynamic programming, where a set of at
+//! most 64 (or 128) nodes is stored in a single integer and used as a hash map or array key.
+use std::{
+    fmt,
+    hash::Hash,
+    ops::{BitAnd, BitOr, BitXor, Sub},
+};
+
+/// `BitSet` is the common interface of [`BitSet64`] and [`BitSet128`], so that algorithms can be
+/// written once over either width.
+pub trait BitSet:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Default
+    + fmt::Debug
+    + BitOr<Output = Self>
+    + BitAnd<Output = Self>
+    + BitXor<Output = Self>
+    + Sub<Output = Self>
+{
+    /// The number of elements the set is able to hold.
+    const CAPACITY: usize;
+
+    /// Returns the empty set.
+    fn empty() -> Self;
+
+    /// Returns the set `{0, 1, ..., n - 1}`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is larger than [`BitSet::CAPACITY`].
+    fn full(n: usize) -> Self;
+
+    /// Returns true if `i` is in the set.
+    fn contains(&self, i: usize) -> bool;
+
+    /// Adds `i` to the set. Returns true if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than [`BitSet::CAPACITY`].
+    fn insert(&mut self, i: usize) -> bool;
+
+    /// Removes `i` from the set. Returns true if it was present, so an `i` the set cannot hold is
+    /// never removed.
+    fn remove(&mut self, i: usize) -> bool;
+
+    /// Returns the number of elements in the set.
+    fn len(&self) -> usize;
+
+    /// Returns true if the set has no elements.
+    fn is_empty(&self) -> bool {
+        *self == Self::empty()
+    }
+
+    /// Returns a copy of the set with `i` added.
+    fn with(mut self, i: usize) -> Self {
+        self.insert(i);
+        self
+    }
+
+    /// Returns a copy of the set with `i` removed.
+    fn without(mut self, i: usize) -> Self {
+        self.remove(i);
+        self
+    }
+
+    /// Returns true if every element of `self` is also in `other`.
+    fn is_subset(&self, other: &Self) -> bool {
+        (*self & *other) == *self
+    }
+
+    /// Returns true if `self` and `other` share no elements.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        (*self & *other).is_empty()
+    }
+}
+
+macro_rules! bitset {
+    ($(#[$meta:meta])* $name:ident, $subsets:ident, $iter:ident, $int:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name($int);
+
+        impl $name {
+            /// Creates a set directly from its bit representation.
+            pub const fn from_bits(bits: $int) -> Self {
+                Self(bits)
+            }
+
+            /// Returns the bit representation of the set.
+            pub const fn bits(&self) -> $int {
+                self.0
+            }
+
+            /// Returns the set containing only `i`.
+            ///
+            /// # Panics
+            ///
+            /// If `i` is not less than [`BitSet::CAPACITY`].
+            pub const fn singleton(i: usize) -> Self {
+                assert!(
+                    i < <$int>::BITS as usize,
+                    concat!("element does not fit in a ", stringify!($name))
+                );
+                Self(1 << i)
+            }
+
+            /// Returns the smallest element of the set.
+            pub fn first(&self) -> Option<usize> {
+                (self.0 != 0).then(|| self.0.trailing_zeros() as usize)
+            }
+
+            /// Returns the largest element of the set.
+            pub fn last(&self) -> Option<usize> {
+                (self.0 != 0).then(|| (<$int>::BITS - 1 - self.0.leading_zeros()) as usize)
+            }
+
+            /// Iterates over the elements of the set in increasing order.
+            pub fn iter(&self) -> $iter {
+                $iter(self.0)
+            }
+
+            /// Iterates over every subset of this set, including the empty set and the set itself.
+            ///
+            /// Subsets are produced in decreasing order of their bit representation, so the set
+            /// itself comes first and the empty set comes last. There are `2^len` of them.
+            pub fn subsets(&self) -> $subsets {
+                $subsets {
+                    set: self.0,
+                    next: Some(self.0),
+                }
+            }
+        }
+
+        impl BitSet for $name {
+            const CAPACITY: usize = <$int>::BITS as usize;
+
+            fn empty() -> Self {
+                Self(0)
+            }
+
+            fn full(n: usize) -> Self {
+                assert!(n <= Self::CAPACITY, "{} elements do not fit in a {}", n, stringify!($name));
+                if n == Self::CAPACITY {
+                    Self(<$int>::MAX)
+                } else {
+                    Self((1 << n) - 1)
+                }
+            }
+
+            fn contains(&self, i: usize) -> bool {
+                i < Self::CAPACITY && self.0 & (1 << i) != 0
+            }
+
+            fn insert(&mut self, i: usize) -> bool {
+                assert!(i < Self::CAPACITY, "{} does not fit in a {}", i, stringify!($name));
+                let had = self.contains(i);
+                self.0 |= 1 << i;
+                !had
+            }
+
+            fn remove(&mut self, i: usize) -> bool {
+                let had = self.contains(i);
+                if had {
+                    self.0 &= !(1 << i);
+                }
+                had
+            }
+
+            fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+        }
+
+        impl BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl BitXor for $name {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self {
+                Self(self.0 ^ rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 & !rhs.0)
+            }
+        }
+
+        impl FromIterator<usize> for $name {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut set = Self(0);
+                for i in iter {
+                    set.insert(i);
+                }
+                set
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = usize;
+            type IntoIter = $iter;
+
+            fn into_iter(self) -> $iter {
+                self.iter()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        /// Iterator over the elements of a bit set, see `iter`.
+        #[derive(Debug, Clone)]
+        pub struct $iter($int);
+
+        impl Iterator for $iter {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                if self.0 == 0 {
+                    return None;
+                }
+                let i = self.0.trailing_zeros() as usize;
+                self.0 &= self.0 - 1;
+                Some(i)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.0.count_ones() as usize;
+                (len, Some(len))
+            }
+        }
+
+        impl ExactSizeIterator for $iter {}
+
+        /// Iterator over the subsets of a bit set, see `subsets`.
+        #[derive(Debug, Clone)]
+        pub struct $subsets {
+            set: $int,
+            next: Option<$int>,
+        }
+
+        impl Iterator for $subsets {
+            type Item = $name;
+
+            fn next(&mut self) -> Option<$name> {
+                let current = self.next?;
+                self.next = (current != 0).then(|| (current - 1) & self.set);
+                Some($name(current))
+            }
+        }
+    };
+}
+
+bitset!(
+    /// A set of integers in `0..64` packed into a `u64`.
+    ///
+    /// ```
+    /// use aoc::bitset::{BitSet, BitSet64};
+    ///
+    /// let mut set = BitSet64::empty();
+    /// set.insert(1);
+    /// set.insert(4);
+    ///
+    /// assert!(set.contains(4));
+    /// assert!(!set.contains(2));
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 4]);
+    ///
+    /// let subsets: Vec<BitSet64> = set.subsets().collect();
+    /// assert_eq!(
+    ///     subsets,
+    ///     vec![
+    ///         [1, 4].into_iter().collect(),
+    ///         BitSet64::singleton(4),
+    ///         BitSet64::singleton(1),
+    ///         BitSet64::empty(),
+    ///     ]
+    /// );
+    /// ```
+    BitSet64,
+    Subsets64,
+    Iter64,
+    u64
+);
+
+bitset!(
+    /// A set of integers in `0..128` packed into a `u128`.
+    ///
+    /// ```
+    /// use aoc::bitset::{BitSet, BitSet128};
+    ///
+    /// let a: BitSet128 = [0, 100, 127].into_iter().collect();
+    /// let b = BitSet128::full(101);
+    ///
+    /// assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![0, 100]);
+    /// assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![127]);
+    /// assert!(!a.is_subset(&b));
+    /// assert_eq!(a.last(), Some(127));
+    /// ```
+    BitSet128,
+    Subsets128,
+    Iter128,
+    u128
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full() {
+        assert_eq!(BitSet64::full(0), BitSet64::empty());
+        assert_eq!(BitSet64::full(3).bits(), 0b111);
+        assert_eq!(BitSet64::full(64).len(), 64);
+        assert_eq!(BitSet128::full(128).len(), 128);
+    }
+
+    #[test]
+    fn test_subsets_count() {
+        let set: BitSet64 = [0, 3, 9, 20, 63].into_iter().collect();
+        let subsets: Vec<_> = set.subsets().collect();
+
+        assert_eq!(subsets.len(), 32);
+        assert!(subsets.iter().all(|s| s.is_subset(&set)));
+        assert_eq!(subsets.first(), Some(&set));
+        assert_eq!(subsets.last(), Some(&BitSet64::empty()));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BitSet128::empty();
+        assert!(set.insert(70));
+        assert!(!set.insert(70));
+        assert!(set.contains(70));
+        assert!(set.remove(70));
+        assert!(!set.remove(70));
+        assert!(set.is_empty());
+    }
+}
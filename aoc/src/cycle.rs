@@ -0,0 +1,279 @@
+//! Cycle detection for iterated functions and simulations.
+//!
+//! Many puzzles ask for the state of a simulation after an absurd number of steps. Eventually the
+//! simulation repeats itself, so after finding the cycle whole periods can be skipped.
+//!
+//! [`brent`] and [`floyd`] use O(1) memory and work on pure `state -> state` functions. The other
+//! detectors remember every state they have seen in a hash map and step the state in place, which
+//! suits simulations that are expensive to clone.
+use std::{collections::HashMap, hash::Hash};
+
+use num_traits::{FromPrimitive, Num};
+
+/// `Cycle` describes the shape of the sequence `x0, f(x0), f(f(x0)), ...`.
+///
+/// The states at steps `start..start + period` repeat forever, the `start` steps before that are
+/// never seen again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the first state that is part of the cycle.
+    pub start: usize,
+    /// The length of the cycle.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// `equivalent_step` returns the first step that has the same state as step `n`.
+    ///
+    /// ```
+    /// use aoc::cycle::Cycle;
+    ///
+    /// let cycle = Cycle { start: 3, period: 4 };
+    /// assert_eq!(cycle.equivalent_step(2), 2);
+    /// assert_eq!(cycle.equivalent_step(7), 3);
+    /// assert_eq!(cycle.equivalent_step(1_000_000_000), 4);
+    /// ```
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Brent's algorithm. Finds the cycle of `start, f(start), f(f(start)), ...` using O(1) memory.
+///
+/// Never returns if the sequence does not cycle.
+///
+/// ```
+/// use aoc::cycle::{brent, Cycle};
+///
+/// // 2, 4, 16, 256 % 55 = 36, 31, 26, 16, ...
+/// let cycle = brent(2u64, |&x| x * x % 55);
+/// assert_eq!(cycle, Cycle { start: 2, period: 4 });
+/// ```
+pub fn brent<S, F>(start: S, f: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: Fn(&S) -> S,
+{
+    // Find the period by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // Walk two pointers `period` apart until they meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    Cycle {
+        start: prefix,
+        period,
+    }
+}
+
+/// Floyd's tortoise and hare. Finds the cycle of `start, f(start), f(f(start)), ...` using O(1)
+/// memory.
+///
+/// Usually slower than [`brent`], but included for completeness. Never returns if the sequence does
+/// not cycle.
+///
+/// ```
+/// use aoc::cycle::{floyd, Cycle};
+///
+/// let cycle = floyd(2u64, |&x| x * x % 55);
+/// assert_eq!(cycle, Cycle { start: 2, period: 4 });
+/// ```
+pub fn floyd<S, F>(start: S, f: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle {
+        start: prefix,
+        period,
+    }
+}
+
+/// Steps `state` until a fingerprint repeats or step `limit` is reached. Calls `visit` with every
+/// state, including the initial one.
+fn run<S, K>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    mut visit: impl FnMut(&S),
+    limit: usize,
+) -> Option<Cycle>
+where
+    K: Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    for i in 0..=limit {
+        if i > 0 {
+            step(state);
+        }
+        visit(state);
+
+        if let Some(first) = seen.insert(key(state), i) {
+            return Some(Cycle {
+                start: first,
+                period: i - first,
+            });
+        }
+    }
+    None
+}
+
+/// Finds the cycle of a simulation by remembering every state it has been in.
+///
+/// `step` advances the state in place. Never returns if the simulation does not cycle.
+///
+/// ```
+/// use aoc::cycle::{detect, Cycle};
+///
+/// let cycle = detect(2u64, |x| *x = *x * *x % 55);
+/// assert_eq!(cycle, Cycle { start: 2, period: 4 });
+/// ```
+pub fn detect<S, F>(start: S, step: F) -> Cycle
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&mut S),
+{
+    detect_by_key(start, step, S::clone)
+}
+
+/// Like [`detect`], but only remembers a fingerprint of each state.
+///
+/// `key` should capture everything that influences the future of the simulation. This is useful
+/// when the state contains an ever growing part (like a height or a tick counter) that would
+/// otherwise prevent it from ever repeating.
+///
+/// ```
+/// use aoc::cycle::{detect_by_key, Cycle};
+///
+/// // (tick, value): the tick keeps growing but does not influence the value
+/// let cycle = detect_by_key((0, 1u64), |(t, x)| (*t, *x) = (*t + 1, *x * 3 % 7), |&(_, x)| x);
+/// assert_eq!(cycle, Cycle { start: 0, period: 6 });
+/// ```
+pub fn detect_by_key<S, K, F, G>(mut start: S, step: F, key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+{
+    run(&mut start, step, key, |_| {}, usize::MAX).unwrap()
+}
+
+/// Returns the state after `n` steps, skipping whole periods once the simulation repeats.
+///
+/// ```
+/// use aoc::cycle::nth;
+///
+/// assert_eq!(nth(2u64, |x| *x = *x * *x % 55, 5), 26);
+/// assert_eq!(nth(2u64, |x| *x = *x * *x % 55, 1_000_000_000_000), 31);
+/// ```
+pub fn nth<S, F>(start: S, step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&mut S),
+{
+    nth_by_key(start, step, S::clone, n)
+}
+
+/// Like [`nth`], but only remembers a fingerprint of each state. See [`detect_by_key`].
+pub fn nth_by_key<S, K, F, G>(mut start: S, step: F, key: G, n: usize) -> S
+where
+    S: Clone,
+    K: Hash + Eq,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+{
+    let mut history = vec![];
+    match run(&mut start, step, key, |s| history.push(s.clone()), n) {
+        Some(cycle) => history.swap_remove(cycle.equivalent_step(n)),
+        None => start,
+    }
+}
+
+/// Returns `value` after `n` steps for a quantity that grows by the same amount every period, like
+/// the height of a tower or a running total.
+///
+/// The states are compared with `key`, which should leave out the growing quantity (see
+/// [`detect_by_key`]). Once the cycle is found, the value at step `n` is the value at the equivalent
+/// step plus the growth of one period for every period skipped.
+///
+/// ```
+/// use aoc::cycle::extrapolate;
+///
+/// // (total, x): x cycles through 1, 3, 2, 6, 4, 5 and total sums it up
+/// let step = |(total, x): &mut (u64, u64)| {
+///     *x = *x * 3 % 7;
+///     *total += *x;
+/// };
+///
+/// // 3 + 2 + 6 + 4 + 5 + 1 = 21 per period
+/// assert_eq!(extrapolate((0, 1), step, |&(_, x)| x, |&(total, _)| total, 6), 21);
+/// assert_eq!(extrapolate((0, 1), step, |&(_, x)| x, |&(total, _)| total, 600_000_000_001), 2_100_000_000_003);
+/// ```
+pub fn extrapolate<S, K, T, F, G, H>(mut start: S, step: F, key: G, mut value: H, n: usize) -> T
+where
+    K: Hash + Eq,
+    T: Num + FromPrimitive + Copy,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+    H: FnMut(&S) -> T,
+{
+    let mut values = vec![];
+    let Some(cycle) = run(&mut start, step, key, |s| values.push(value(s)), n) else {
+        return value(&start);
+    };
+
+    let periods = (n - cycle.start) / cycle.period;
+    let growth = values[cycle.start + cycle.period] - values[cycle.start];
+    let periods = T::from_usize(periods).expect("number of periods does not fit in the value type");
+
+    values[cycle.equivalent_step(n)] + periods * growth
+}
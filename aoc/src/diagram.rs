@@ -0,0 +1,165 @@
+//! ASCII drawings where the position of a character matters, like crate stacks or numbers written
+//! in columns.
+//!
+//! Editors and puzzle inputs tend to trim trailing spaces, so a [`Diagram`] pads every row with
+//! spaces to the width of the longest one first.
+use std::ops::Range;
+
+/// `Diagram` is a block of text as a rectangle of characters.
+///
+/// ```
+/// use aoc::diagram::Diagram;
+///
+/// let worksheet = Diagram::new("123 328\n 45 64\n  6 98\n*   +");
+/// assert_eq!((worksheet.width(), worksheet.height()), (7, 4));
+/// assert_eq!(worksheet.blocks(), [0..3, 4..7]);
+///
+/// // The second problem, one column at a time
+/// let columns = worksheet.block(4..7);
+/// assert_eq!(columns[0], ['3', '6', '9', '+']);
+/// assert_eq!(columns[2], ['8', ' ', ' ', ' ']);
+/// assert_eq!(worksheet.row_text(1, 4..7), "64 ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagram {
+    rows: Vec<Vec<char>>,
+    width: usize,
+}
+
+impl Diagram {
+    pub fn new(text: &str) -> Self {
+        let mut rows: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, ' ');
+        }
+        Self { rows, width }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The rows, all padded to the same width.
+    pub fn rows(&self) -> &[Vec<char>] {
+        &self.rows
+    }
+
+    /// The character at column `x` of row `y`.
+    pub fn get(&self, x: usize, y: usize) -> char {
+        self.rows[y][x]
+    }
+
+    /// Column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> Vec<char> {
+        self.rows.iter().map(|row| row[x]).collect()
+    }
+
+    /// The part of row `y` in the columns `range`.
+    pub fn row_text(&self, y: usize, range: Range<usize>) -> String {
+        self.rows[y][range].iter().collect()
+    }
+
+    /// `true` if column `x` is all spaces.
+    pub fn is_blank_column(&self, x: usize) -> bool {
+        self.rows.iter().all(|row| row[x] == ' ')
+    }
+
+    /// The ranges of columns between the blank ones, left to right.
+    pub fn blocks(&self) -> Vec<Range<usize>> {
+        let mut blocks = vec![];
+        let mut start = None;
+        for x in 0..=self.width {
+            let blank = x == self.width || self.is_blank_column(x);
+            match (start, blank) {
+                (None, false) => start = Some(x),
+                (Some(s), true) => {
+                    blocks.push(s..x);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        blocks
+    }
+
+    /// The columns `range`, each one top to bottom.
+    pub fn block(&self, range: Range<usize>) -> Vec<Vec<char>> {
+        range.map(|x| self.column(x)).collect()
+    }
+
+    /// The column of every stack. If the last row is a row of labels like ` 1   2   3 `, each label
+    /// marks a stack. Otherwise every column with a letter or digit in it is one.
+    pub fn stack_columns(&self) -> Vec<usize> {
+        match self.label_row() {
+            Some(labels) => {
+                let above = &self.rows[..self.height() - 1];
+                let mut columns = vec![];
+                let mut x = 0;
+                while x < self.width {
+                    if labels[x] == ' ' {
+                        x += 1;
+                        continue;
+                    }
+                    let end = (x..self.width)
+                        .find(|&x| labels[x] == ' ')
+                        .unwrap_or(self.width);
+                    // A label wider than the crates above it is lined up with one of its digits
+                    let column = (x..end)
+                        .find(|&x| above.iter().any(|row| row[x].is_alphanumeric()))
+                        .unwrap_or(x);
+                    columns.push(column);
+                    x = end;
+                }
+                columns
+            }
+            None => (0..self.width)
+                .filter(|&x| self.rows.iter().any(|row| row[x].is_alphanumeric()))
+                .collect(),
+        }
+    }
+
+    /// The last row, if it only contains numbers.
+    fn label_row(&self) -> Option<&[char]> {
+        let last = self.rows.last()?;
+        let is_labels = last.iter().any(|c| c.is_ascii_digit())
+            && last.iter().all(|&c| c == ' ' || c.is_ascii_digit());
+        is_labels.then_some(last)
+    }
+
+    /// The stacks in the drawing, each from the bottom to the top. Only letters and digits count as
+    /// items, brackets and the label row are dropped.
+    ///
+    /// ```
+    /// use aoc::diagram::Diagram;
+    ///
+    /// let drawing = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3";
+    /// let stacks = Diagram::new(drawing).stacks();
+    /// assert_eq!(stacks, [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+    ///
+    /// // Without labels the stacks are found from the letters
+    /// let stacks = Diagram::new("  B\nA C").stacks();
+    /// assert_eq!(stacks, [vec!['A'], vec!['C', 'B']]);
+    /// ```
+    pub fn stacks(&self) -> Vec<Vec<char>> {
+        let items = match self.label_row() {
+            Some(_) => &self.rows[..self.height() - 1],
+            None => &self.rows[..],
+        };
+        self.stack_columns()
+            .into_iter()
+            .map(|x| {
+                items
+                    .iter()
+                    .rev()
+                    .map(|row| row[x])
+                    .filter(|c| c.is_alphanumeric())
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,243 @@
+//! Numbers as sequences of digits.
+//!
+//! Every function takes the base explicitly and expects non-negative numbers.
+use std::ops::RangeInclusive;
+
+use num_traits::PrimInt;
+
+/// The number of digits of `n`. Zero has one digit.
+///
+/// ```
+/// use aoc::digits::count_digits;
+///
+/// assert_eq!(count_digits(0, 10), 1);
+/// assert_eq!(count_digits(999, 10), 3);
+/// assert_eq!(count_digits(1000, 10), 4);
+/// assert_eq!(count_digits(u64::MAX, 10), 20);
+/// assert_eq!(count_digits(0b1011, 2), 4);
+/// ```
+pub fn count_digits<T: PrimInt>(mut n: T, base: T) -> u32 {
+    let mut count = 1;
+    while n >= base {
+        n = n / base;
+        count += 1;
+    }
+    count
+}
+
+/// The digits of `n`, most significant first.
+///
+/// ```
+/// use aoc::digits::digits;
+///
+/// assert!(digits(1204, 10).eq([1, 2, 0, 4]));
+/// assert!(digits(0, 10).eq([0]));
+/// assert!(digits(0xbeefu32, 16).eq([0xb, 0xe, 0xe, 0xf]));
+/// assert!(digits(1204, 10).rev().eq([4, 0, 2, 1]));
+/// ```
+pub fn digits<T: PrimInt>(n: T, base: T) -> Digits<T> {
+    Digits {
+        n,
+        base,
+        len: count_digits(n, base),
+    }
+}
+
+/// The iterator returned by [`digits`].
+#[derive(Debug, Clone)]
+pub struct Digits<T> {
+    /// The digits not yet returned
+    n: T,
+    base: T,
+    len: u32,
+}
+
+impl<T: PrimInt> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let power = self.base.pow(self.len);
+        let digit = self.n / power;
+        self.n = self.n % power;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl<T: PrimInt> DoubleEndedIterator for Digits<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let digit = self.n % self.base;
+        self.n = self.n / self.base;
+        Some(digit)
+    }
+}
+
+impl<T: PrimInt> ExactSizeIterator for Digits<T> {}
+
+/// The number with the given digits, most significant first.
+///
+/// ```
+/// use aoc::digits::from_digits;
+///
+/// assert_eq!(from_digits([1, 2, 0, 4], 10), 1204);
+/// assert_eq!(from_digits([1u8, 0, 1, 1], 2), 11);
+/// assert_eq!(from_digits([], 10), 0);
+/// ```
+pub fn from_digits<T: PrimInt>(digits: impl IntoIterator<Item = T>, base: T) -> T {
+    digits
+        .into_iter()
+        .fold(T::zero(), |n, digit| n * base + digit)
+}
+
+/// Every number in `range` that is a block of digits repeated some number of times in `repeats`,
+/// sorted. Leading zeros are not allowed, so `0101` does not count.
+///
+/// Instead of checking every number in the range, this goes over the possible lengths. A number of
+/// `len` digits made of `r` copies of a `len / r` digit block is the block times `1 0..01 0..01`, so
+/// the blocks that land in `range` form a range too.
+///
+/// ```
+/// use aoc::digits::repeated_blocks;
+///
+/// assert_eq!(repeated_blocks(95..=115, 2..=2, 10), [99]);
+/// assert_eq!(repeated_blocks(95..=115, 2..=u32::MAX, 10), [99, 111]);
+/// assert_eq!(repeated_blocks(998..=1012, 2..=u32::MAX, 10), [999, 1010]);
+///
+/// // 111111 is 1 six times, 11 three times and 111 twice, but only counts once
+/// assert_eq!(repeated_blocks(111_111..=111_111, 2..=6, 10), [111_111]);
+///
+/// // Every ten digit number is 9 billion numbers to check, but only 90000 blocks
+/// let halves = repeated_blocks(1_000_000_000u64..=9_999_999_999, 2..=2, 10);
+/// assert_eq!(halves.len(), 90_000);
+/// assert_eq!(halves[..2], [1_000_010_000, 1_000_110_001]);
+/// ```
+pub fn repeated_blocks<T: PrimInt>(
+    range: RangeInclusive<T>,
+    repeats: RangeInclusive<u32>,
+    base: T,
+) -> Vec<T> {
+    let (lo, hi) = range.into_inner();
+    let mut numbers = vec![];
+    if lo > hi {
+        return numbers;
+    }
+
+    for len in count_digits(lo, base)..=count_digits(hi, base) {
+        for r in (*repeats.start()).max(2)..=(*repeats.end()).min(len) {
+            if len % r != 0 {
+                continue;
+            }
+            let block_len = len / r;
+
+            // 1 followed by block_len - 1 zeros, r times
+            let Some(multiplier) = (0..r).try_fold(T::zero(), |m, _| {
+                m.checked_mul(&base.pow(block_len))?.checked_add(&T::one())
+            }) else {
+                continue;
+            };
+
+            let smallest_block = base.pow(block_len - 1);
+            let largest_block = base.pow(block_len - 1) * base - T::one();
+            let ceil = lo / multiplier
+                + if lo % multiplier > T::zero() {
+                    T::one()
+                } else {
+                    T::zero()
+                };
+            let first = ceil.max(smallest_block);
+            let last = (hi / multiplier).min(largest_block);
+
+            let mut block = first;
+            while block <= last {
+                numbers.push(block * multiplier);
+                block = block + T::one();
+            }
+        }
+    }
+
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// The largest number that can be formed by keeping `k` of the `digits` in order, as its digits.
+/// `None` if there are fewer than `k` digits.
+///
+/// Greedy with a stack: a digit replaces every smaller digit before it, as long as enough digits
+/// remain to fill up `k`.
+///
+/// ```
+/// use aoc::digits::{largest_subsequence, smallest_subsequence};
+///
+/// assert_eq!(largest_subsequence(&[8, 1, 8, 1, 8, 1, 9, 1, 1], 4), Some(vec![8, 9, 1, 1]));
+/// assert_eq!(smallest_subsequence(&[8, 1, 8, 1, 8, 1, 9, 1, 1], 4), Some(vec![1, 1, 1, 1]));
+/// assert_eq!(smallest_subsequence(&[3, 1, 4, 1, 5], 3), Some(vec![1, 1, 5]));
+/// assert_eq!(largest_subsequence(&[1, 2], 3), None);
+/// ```
+pub fn largest_subsequence<T: Ord + Copy>(digits: &[T], k: usize) -> Option<Vec<T>> {
+    subsequence(digits, k, |kept, digit| kept < digit)
+}
+
+/// The smallest number that can be formed by keeping `k` of the `digits` in order, as its digits.
+/// `None` if there are fewer than `k` digits. See [`largest_subsequence`].
+pub fn smallest_subsequence<T: Ord + Copy>(digits: &[T], k: usize) -> Option<Vec<T>> {
+    subsequence(digits, k, |kept, digit| kept > digit)
+}
+
+fn subsequence<T: Copy>(digits: &[T], k: usize, replace: impl Fn(T, T) -> bool) -> Option<Vec<T>> {
+    if digits.len() < k {
+        return None;
+    }
+
+    let mut kept = Vec::with_capacity(k);
+    for (i, &digit) in digits.iter().enumerate() {
+        let remaining = digits.len() - i;
+        while kept.last().is_some_and(|&last| replace(last, digit)) && kept.len() + remaining > k {
+            kept.pop();
+        }
+        if kept.len() < k {
+            kept.push(digit);
+        }
+    }
+    Some(kept)
+}
+
+/// The names of the digits one to nine. Zero does not count as spelled out in puzzles.
+pub const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The digits in `line`, written either as a digit or as one of the [`DIGIT_NAMES`], in order.
+///
+/// Names may overlap, `eightwo` is both 8 and 2.
+///
+/// ```
+/// use aoc::digits::spelled_digits;
+///
+/// assert!(spelled_digits("xtwone3four").eq([2, 1, 3, 4]));
+/// assert!(spelled_digits("7pqrstsixteen").eq([7, 6]));
+/// assert!(spelled_digits("eightwothree").rev().eq([3, 2, 8]));
+/// ```
+pub fn spelled_digits(line: &str) -> impl DoubleEndedIterator<Item = u32> + '_ {
+    (0..line.len()).filter_map(|i| {
+        let rest = &line.as_bytes()[i..];
+        if rest[0].is_ascii_digit() {
+            return Some((rest[0] - b'0') as u32);
+        }
+        DIGIT_NAMES
+            .iter()
+            .position(|name| rest.starts_with(name.as_bytes()))
+            .map(|n| n as u32 + 1)
+    })
+}
@@ -0,0 +1,203 @@
+//! Errors in puzzle inputs that say where the input is wrong, instead of an `unwrap` backtrace.
+//!
+//! A [`ParseError`] keeps the line it happened in, so [`run`] can print it under the message with a
+//! caret at the bad character:
+//!
+//! ```text
+//! error: expected '.', '#' or 'O'
+//!  --> line 2, column 4
+//!   |
+//! 2 | O.O?#....#
+//!   |    ^
+//! ```
+use std::fmt;
+
+/// `ParseError` is where and why parsing failed. Lines and columns start at 1, columns count
+/// characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The text of the line, without the line break
+    pub snippet: String,
+    /// What the parser was looking for
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// An error at byte offset `pos` of `input`.
+    ///
+    /// ```
+    /// use aoc::error::ParseError;
+    ///
+    /// let error = ParseError::at("1,2\r\n3,x\r\n", 7, "integer");
+    /// assert_eq!((error.line, error.column), (2, 3));
+    /// assert_eq!(error.snippet, "3,x");
+    /// ```
+    pub fn at(input: &str, pos: usize, expected: impl Into<String>) -> Self {
+        let before = &input[..pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[pos..].find('\n').map_or(input.len(), |i| pos + i);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// The error with the line number moved down by `lines`, for errors found in a single line or
+    /// section that was cut out of the whole input.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// The error as a message, the line it is in and a caret under the column.
+    ///
+    /// ```
+    /// use aoc::error::ParseError;
+    ///
+    /// let error = ParseError::at("#.\n.x", 4, "'#' or '.'");
+    /// assert_eq!(
+    ///     error.render(),
+    ///     "error: expected '#' or '.'\n --> line 2, column 2\n  |\n2 | .x\n  |  ^\n"
+    /// );
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Keep tabs so that the caret lines up with the character above it
+        let indent: String = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "error: expected {}\n{gutter}--> line {}, column {}\n{gutter} |\n{number} | {}\n{gutter} | {indent}^\n",
+            self.expected, self.line, self.column, self.snippet
+        )
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Runs a solution, and if the input does not parse prints the error with [`ParseError::render`]
+/// and exits with status 1.
+///
+/// ```no_run
+/// use aoc::error::{parse_lines, run, ParseError};
+///
+/// // In `main`, prints the third line with a caret under the `t`
+/// run(|| {
+///     let numbers = parse_lines("1\n2\nthree", |line| {
+///         line.parse::<u32>()
+///             .map_err(|_| ParseError::at(line, 0, "a number"))
+///     })?;
+///     println!("{}", numbers.iter().sum::<u32>());
+///     Ok(())
+/// });
+/// ```
+pub fn run(solution: impl FnOnce() -> Result<()>) {
+    if let Err(error) = solution() {
+        eprint!("{}", error.render());
+        std::process::exit(1);
+    }
+}
+
+/// Parses every line of `input` with `f`. `f` sees one line at a time, so the line numbers of its
+/// errors are corrected to where the line is in the input.
+///
+/// ```
+/// use aoc::error::{parse_lines, ParseError};
+/// use aoc::scan;
+///
+/// let input = "p=0,4 v=3,-3\np=6,3 v=-1,x";
+/// let error = parse_lines(input, |line| {
+///     scan!(line, "p={},{} v={},{}" => i32, i32, i32, i32)
+/// })
+/// .unwrap_err();
+/// assert_eq!((error.line, error.column), (2, 12));
+/// assert_eq!(error.snippet, "p=6,3 v=-1,x");
+/// ```
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|error| error.offset_lines(i)))
+        .collect()
+}
+
+/// Parses a rectangle of characters, one `cell` per character. `cell` returns `None` for characters
+/// that are not allowed, and `expected` describes the ones that are.
+///
+/// Every row has to be as wide as the first one, so a truncated input is reported where it ends
+/// instead of as an index out of bounds later on.
+///
+/// ```
+/// use aoc::error::parse_grid;
+///
+/// let wall = |c| match c {
+///     '#' => Some(true),
+///     '.' => Some(false),
+///     _ => None,
+/// };
+///
+/// let grid = parse_grid("#.\r\n.#\r\n", wall, "'#' or '.'").unwrap();
+/// assert_eq!(grid, [[true, false], [false, true]]);
+///
+/// let error = parse_grid("#.\n.x", wall, "'#' or '.'").unwrap_err();
+/// assert_eq!(error.to_string(), "line 2, column 2: expected '#' or '.'");
+///
+/// let error = parse_grid("#..\n.#", wall, "'#' or '.'").unwrap_err();
+/// assert_eq!(error.to_string(), "line 2, column 3: expected row of 3 cells");
+/// ```
+pub fn parse_grid<T>(
+    input: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+    expected: &str,
+) -> Result<Vec<Vec<T>>> {
+    let mut width = None;
+    let mut rows = vec![];
+    for (y, line) in input.lines().enumerate() {
+        let error = |x: usize, expected: String| ParseError {
+            line: y + 1,
+            column: x + 1,
+            snippet: line.to_string(),
+            expected,
+        };
+
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(x, c)| cell(c).ok_or_else(|| error(x, expected.to_string())))
+            .collect::<Result<Vec<T>>>()?;
+
+        let width = *width.get_or_insert(row.len());
+        if row.len() != width {
+            let x = row.len().min(width);
+            return Err(error(x, format!("row of {width} cells")));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
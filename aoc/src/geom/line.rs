@@ -0,0 +1,365 @@
+//! Intersections of lines, rays and segments, computed exactly.
+//!
+//! Puzzle inputs happily use coordinates around 10^14, where floats silently round the answer away.
+//! Everything here uses `i128` and [`Rational`] instead. Cross products of the inputs have to fit in
+//! an `i128`, and so do the intersection points once they are written as fractions, which is plenty
+//! for large positions with small velocities or for moderate coordinates all around.
+//!
+//! Lines, rays and segments are all parametric paths `origin + t * direction`, they only differ in
+//! the allowed values of `t`. A [`Ray`] can also be read as something moving with a constant
+//! velocity, where `t` is the time.
+use num_traits::Zero;
+
+use crate::math::Rational;
+
+pub type Point = (i128, i128);
+
+type Q = Rational<i128>;
+
+fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn cross(a: Point, b: Point) -> i128 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn dot(a: Point, b: Point) -> i128 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+/// `Path` is a set of points `origin() + t * direction()` for every `t` in `t_range()`.
+pub trait Path {
+    fn origin(&self) -> Point;
+
+    /// Never `(0, 0)`.
+    fn direction(&self) -> Point;
+
+    /// The smallest and largest allowed `t`, `None` if unbounded.
+    fn t_range(&self) -> (Option<i128>, Option<i128>);
+
+    /// The point at parameter `t`, whether or not `t` is in range.
+    fn point_at(&self, t: Q) -> (Q, Q) {
+        let (ox, oy) = self.origin();
+        let (dx, dy) = self.direction();
+        (Q::from(ox) + t * Q::from(dx), Q::from(oy) + t * Q::from(dy))
+    }
+
+    fn allows(&self, t: Q) -> bool {
+        let (lo, hi) = self.t_range();
+        lo.is_none_or(|lo| Q::from(lo) <= t) && hi.is_none_or(|hi| t <= Q::from(hi))
+    }
+}
+
+/// The infinite line through `origin` along `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub origin: Point,
+    pub direction: Point,
+}
+
+impl Line {
+    /// The line through two different points.
+    pub fn through(a: Point, b: Point) -> Self {
+        Self {
+            origin: a,
+            direction: sub(b, a),
+        }
+    }
+
+    /// `true` if `p` is on the line.
+    pub fn contains(&self, p: Point) -> bool {
+        cross(self.direction, sub(p, self.origin)) == 0
+    }
+}
+
+impl Path for Line {
+    fn origin(&self) -> Point {
+        self.origin
+    }
+
+    fn direction(&self) -> Point {
+        self.direction
+    }
+
+    fn t_range(&self) -> (Option<i128>, Option<i128>) {
+        (None, None)
+    }
+}
+
+/// The half line starting at `origin`, or something at `origin` moving by `velocity` every step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub origin: Point,
+    pub velocity: Point,
+}
+
+impl Ray {
+    /// Where the ray is at time `t`.
+    pub fn position(&self, t: i128) -> Point {
+        (
+            self.origin.0 + t * self.velocity.0,
+            self.origin.1 + t * self.velocity.1,
+        )
+    }
+
+    /// The time at which `self` and `other` are at the same point at the same time, if they ever
+    /// are (now or in the future). Crossing paths at different times does not count, see
+    /// [`intersect`] for that.
+    ///
+    /// ```
+    /// use aoc::geom::line::Ray;
+    /// use aoc::math::Rational;
+    ///
+    /// let a = Ray { origin: (0, 0), velocity: (2, 1) };
+    /// let b = Ray { origin: (10, -2), velocity: (-3, 2) };
+    /// assert_eq!(a.collision_time(&b), Some(Rational::from(2)));
+    ///
+    /// let c = Ray { origin: (0, 5), velocity: (2, 1) };
+    /// assert_eq!(a.collision_time(&c), None);
+    /// assert_eq!(b.collision_time(&Ray { origin: (13, -2), velocity: (-3, 2) }), None);
+    /// ```
+    pub fn collision_time(&self, other: &Ray) -> Option<Q> {
+        // origin + t * velocity are equal when dp + t * dv = 0
+        let dp = sub(self.origin, other.origin);
+        let dv = sub(self.velocity, other.velocity);
+        if dv == (0, 0) {
+            return (dp == (0, 0)).then(Q::zero);
+        }
+        if cross(dp, dv) != 0 {
+            return None;
+        }
+
+        let t = Q::new(-dot(dp, dv), dot(dv, dv));
+        (t >= Q::zero()).then_some(t)
+    }
+
+    /// The time at which the ray passes through `p`, if it ever does.
+    ///
+    /// ```
+    /// use aoc::geom::line::Ray;
+    /// use aoc::math::Rational;
+    ///
+    /// let r = Ray { origin: (1, 1), velocity: (2, 4) };
+    /// assert_eq!(r.time_at((4, 7)), Some(Rational::new(3, 2)));
+    /// assert_eq!(r.time_at((0, -1)), None);
+    /// ```
+    pub fn time_at(&self, p: Point) -> Option<Q> {
+        let v = sub(p, self.origin);
+        if cross(self.velocity, v) != 0 {
+            return None;
+        }
+        let t = Q::new(dot(v, self.velocity), dot(self.velocity, self.velocity));
+        (t >= Q::zero()).then_some(t)
+    }
+}
+
+impl Path for Ray {
+    fn origin(&self) -> Point {
+        self.origin
+    }
+
+    fn direction(&self) -> Point {
+        self.velocity
+    }
+
+    fn t_range(&self) -> (Option<i128>, Option<i128>) {
+        (Some(0), None)
+    }
+}
+
+/// The segment from `start` to `end`, both included. `t` goes from 0 at `start` to 1 at `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// `true` if `p` is on the segment, end points included.
+    ///
+    /// ```
+    /// use aoc::geom::line::Segment;
+    ///
+    /// let s = Segment { start: (0, 0), end: (6, 3) };
+    /// assert!(s.contains((2, 1)) && s.contains((6, 3)));
+    /// assert!(!s.contains((8, 4)) && !s.contains((1, 1)));
+    /// ```
+    pub fn contains(&self, p: Point) -> bool {
+        let d = self.direction();
+        let v = sub(p, self.start);
+        cross(d, v) == 0 && (0..=dot(d, d)).contains(&dot(d, v))
+    }
+
+    /// The time along the segment, `0` at `start` and `1` at `end`, at which it reaches the point
+    /// closest to `p` on its line.
+    pub fn project(&self, p: Point) -> Q {
+        let d = self.direction();
+        Q::new(dot(sub(p, self.start), d), dot(d, d))
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self {
+            start: self.end,
+            end: self.start,
+        }
+    }
+}
+
+impl Path for Segment {
+    fn origin(&self) -> Point {
+        self.start
+    }
+
+    fn direction(&self) -> Point {
+        sub(self.end, self.start)
+    }
+
+    fn t_range(&self) -> (Option<i128>, Option<i128>) {
+        (Some(0), Some(1))
+    }
+}
+
+/// How two paths meet, see [`intersect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intersection {
+    /// Exactly one common point, at parameter `t` on the first path and `u` on the second.
+    Point { point: (Q, Q), t: Q, u: Q },
+    /// Collinear with infinitely many common points. They are the points of the first path with
+    /// `t` from `start` to `end`, `None` if unbounded.
+    Overlap { start: Option<Q>, end: Option<Q> },
+    /// Parallel and never meeting.
+    Parallel,
+    /// Not parallel, or collinear, but not meeting within the extent of the paths.
+    None,
+}
+
+impl Intersection {
+    /// The common point, if there is exactly one.
+    pub fn point(&self) -> Option<(Q, Q)> {
+        match self {
+            Self::Point { point, .. } => Some(*point),
+            _ => None,
+        }
+    }
+}
+
+/// Intersects two paths exactly. Panics if either has a zero direction.
+///
+/// ```
+/// use aoc::geom::line::{intersect, Intersection, Line, Ray, Segment};
+/// use aoc::math::Rational;
+///
+/// let a = Segment { start: (0, 0), end: (4, 4) };
+/// let b = Segment { start: (0, 4), end: (4, 0) };
+/// let two = Rational::from(2);
+/// let half = Rational::new(1, 2);
+/// assert_eq!(intersect(&a, &b), Intersection::Point { point: (two, two), t: half, u: half });
+///
+/// // The lines cross at (5, 5), past the end of the segment
+/// let c = Segment { start: (6, 4), end: (10, 0) };
+/// assert_eq!(intersect(&a, &c), Intersection::None);
+/// assert_eq!(intersect(&Line::through((0, 0), (4, 4)), &c), Intersection::None);
+/// assert!(intersect(&Line::through((0, 0), (4, 4)), &Line::through((6, 4), (10, 0))).point().is_some());
+///
+/// let d = Segment { start: (0, 1), end: (4, 5) };
+/// assert_eq!(intersect(&a, &d), Intersection::Parallel);
+///
+/// // Collinear segments overlapping from (2, 2) to (4, 4), which is t = 1/2 to 1 on `a`
+/// let e = Segment { start: (6, 6), end: (2, 2) };
+/// assert_eq!(intersect(&a, &e), Intersection::Overlap { start: Some(half), end: Some(Rational::from(1)) });
+///
+/// // Touching end to end is a single point
+/// let f = Segment { start: (4, 4), end: (9, 9) };
+/// assert_eq!(intersect(&a, &f).point(), Some((Rational::from(4), Rational::from(4))));
+///
+/// // Rays only look forward
+/// let r = Ray { origin: (3, 0), velocity: (0, 1) };
+/// assert!(intersect(&a, &r).point().is_some());
+/// let r = Ray { origin: (3, 0), velocity: (0, -1) };
+/// assert_eq!(intersect(&a, &r), Intersection::None);
+/// ```
+pub fn intersect(a: &impl Path, b: &impl Path) -> Intersection {
+    let (p, r) = (a.origin(), a.direction());
+    let (q, s) = (b.origin(), b.direction());
+    assert!(r != (0, 0) && s != (0, 0), "path without a direction");
+
+    let qp = sub(q, p);
+    let denom = cross(r, s);
+    if denom != 0 {
+        let t = Q::new(cross(qp, s), denom);
+        let u = Q::new(cross(qp, r), denom);
+        return if a.allows(t) && b.allows(u) {
+            Intersection::Point {
+                point: a.point_at(t),
+                t,
+                u,
+            }
+        } else {
+            Intersection::None
+        };
+    }
+
+    if cross(qp, r) != 0 {
+        return Intersection::Parallel;
+    }
+
+    // Collinear: b's points q + u * s are at t = (qp . r + u * (s . r)) / (r . r) on a
+    let rr = dot(r, r);
+    let to_t = |u: i128| Q::new(dot(qp, r) + u * dot(s, r), rr);
+    let (mut lo, mut hi) = b.t_range();
+    if dot(s, r) < 0 {
+        (lo, hi) = (hi, lo);
+    }
+    let (b_lo, b_hi) = (lo.map(to_t), hi.map(to_t));
+
+    let (a_lo, a_hi) = a.t_range();
+    let start = max_bound(a_lo.map(Q::from), b_lo);
+    let end = match (a_hi.map(Q::from), b_hi) {
+        (Some(x), Some(y)) => Some(x.min(y)),
+        (x, y) => x.or(y),
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start > end => Intersection::None,
+        (Some(start), Some(end)) if start == end => {
+            // u from t, the inverse of `to_t`
+            let u = (start * Q::from(rr) - Q::from(dot(qp, r))) / Q::from(dot(s, r));
+            Intersection::Point {
+                point: a.point_at(start),
+                t: start,
+                u,
+            }
+        }
+        _ => Intersection::Overlap { start, end },
+    }
+}
+
+fn max_bound(a: Option<Q>, b: Option<Q>) -> Option<Q> {
+    match (a, b) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (x, y) => x.or(y),
+    }
+}
+
+/// `true` if the paths of two rays cross at a single point inside the box from `min` to `max`
+/// (inclusive). The rays do not need to be there at the same time, see [`Ray::collision_time`] for
+/// that. Collinear rays are not considered crossing.
+///
+/// ```
+/// use aoc::geom::line::{crosses_within, Ray};
+///
+/// let a = Ray { origin: (19, 13), velocity: (-2, 1) };
+/// let b = Ray { origin: (18, 19), velocity: (-1, -1) };
+/// let c = Ray { origin: (20, 19), velocity: (1, -5) };
+/// assert!(crosses_within(&a, &b, (7, 7), (27, 27)));
+/// // They cross, but in the past for `c`
+/// assert!(!crosses_within(&a, &c, (7, 7), (27, 27)));
+/// ```
+pub fn crosses_within(a: &Ray, b: &Ray, min: Point, max: Point) -> bool {
+    let Some((x, y)) = intersect(a, b).point() else {
+        return false;
+    };
+    let inside = |v: Q, lo: i128, hi: i128| Q::from(lo) <= v && v <= Q::from(hi);
+    inside(x, min.0, max.0) && inside(y, min.1, max.1)
+}
@@ -0,0 +1,176 @@
+//! Manhattan (L1) distance geometry.
+//!
+//! The points within Manhattan distance `r` of a center form a diamond. Rotating by 45 degrees with
+//! `u = x + y` and `v = x - y` turns every diamond into an axis-aligned square, which makes unions
+//! and intersections easy. Only `(u, v)` with `u` and `v` of the same parity map back to lattice
+//! points.
+use std::ops::Range;
+
+use super::rect::{union_area, Rect};
+use crate::interval::IntervalSet;
+
+pub type Point = (i64, i64);
+
+/// The Manhattan distance between two points.
+pub fn distance(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Rotates `(x, y)` to `(u, v) = (x + y, x - y)`.
+pub fn rotate((x, y): Point) -> Point {
+    (x + y, x - y)
+}
+
+/// The inverse of [`rotate`]. `None` if `(u, v)` is not a lattice point in the original
+/// coordinates.
+///
+/// ```
+/// use aoc::geom::manhattan::{rotate, unrotate};
+///
+/// assert_eq!(rotate((3, -5)), (-2, 8));
+/// assert_eq!(unrotate((-2, 8)), Some((3, -5)));
+/// assert_eq!(unrotate((1, 2)), None);
+/// ```
+pub fn unrotate((u, v): Point) -> Option<Point> {
+    ((u + v) % 2 == 0).then(|| ((u + v) / 2, (u - v) / 2))
+}
+
+/// `Diamond` is the set of points within Manhattan distance `radius` of `center`.
+///
+/// ```
+/// use aoc::geom::manhattan::Diamond;
+///
+/// let d = Diamond::through((8, 7), (2, 10));
+/// assert_eq!(d.radius, 9);
+/// assert!(d.contains((8, -2)) && !d.contains((9, -2)));
+/// assert_eq!(d.row(10), Some(2..15));
+/// assert_eq!(d.row(17), None);
+/// assert_eq!(d.lattice_points(), 181);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diamond {
+    pub center: Point,
+    pub radius: i64,
+}
+
+impl Diamond {
+    pub fn new(center: Point, radius: i64) -> Self {
+        Self { center, radius }
+    }
+
+    /// The smallest diamond around `center` that contains `edge`.
+    pub fn through(center: Point, edge: Point) -> Self {
+        Self::new(center, distance(center, edge))
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        distance(self.center, p) <= self.radius
+    }
+
+    /// The x coordinates covered in row `y`, if any.
+    pub fn row(&self, y: i64) -> Option<Range<i64>> {
+        let half = self.radius - (y - self.center.1).abs();
+        (half >= 0).then(|| self.center.0 - half..self.center.0 + half + 1)
+    }
+
+    /// The diamond as a square in rotated coordinates, see [`rotate`]. Includes the `(u, v)` that
+    /// do not map back to lattice points.
+    pub fn rotated(&self) -> Rect<i64> {
+        let (u, v) = rotate(self.center);
+        let r = self.radius;
+        Rect::inclusive((u - r, v - r), (u + r, v + r))
+    }
+
+    /// The number of lattice points in the diamond.
+    pub fn lattice_points(&self) -> i64 {
+        2 * self.radius * (self.radius + 1) + 1
+    }
+}
+
+/// The x coordinates covered by at least one diamond in row `y`.
+///
+/// ```
+/// use aoc::geom::manhattan::{row_coverage, Diamond};
+///
+/// let diamonds = [Diamond::new((0, 0), 2), Diamond::new((4, 1), 1), Diamond::new((10, 0), 1)];
+/// assert_eq!(row_coverage(&diamonds, 0).ranges(), [-2..3, 4..5, 9..12]);
+/// assert_eq!(row_coverage(&diamonds, 1).ranges(), [-1..2, 3..6, 10..11]);
+/// ```
+pub fn row_coverage(diamonds: &[Diamond], y: i64) -> IntervalSet<i64> {
+    diamonds.iter().filter_map(|d| d.row(y)).collect()
+}
+
+/// Finds a point in the box from `min` to `max` (inclusive) that no diamond covers.
+///
+/// The search only looks at points right next to the borders of two diamonds, where the borders'
+/// outer neighbours cross. That finds the gap when it is surrounded by diamonds, which is how
+/// puzzles hide a single uncovered point, and the corners of the box.
+///
+/// ```
+/// use aoc::geom::manhattan::{find_uncovered, Diamond};
+///
+/// // Four small diamonds around (5, 5), which is the only point of the box they leave uncovered
+/// let diamonds = [(4, 4), (4, 6), (6, 4), (6, 6)].map(|center| Diamond::new(center, 1));
+/// assert_eq!(find_uncovered(&diamonds, (4, 4), (6, 6)), Some((5, 5)));
+/// assert_eq!(find_uncovered(&[Diamond::new((0, 0), 20)], (1, 1), (9, 9)), None);
+/// ```
+pub fn find_uncovered(diamonds: &[Diamond], min: Point, max: Point) -> Option<Point> {
+    // Just outside every diamond border lie the lines u = const and v = const
+    let mut us = vec![];
+    let mut vs = vec![];
+    for d in diamonds {
+        let (u, v) = rotate(d.center);
+        let r = d.radius + 1;
+        us.extend([u - r, u + r]);
+        vs.extend([v - r, v + r]);
+    }
+    us.sort_unstable();
+    us.dedup();
+    vs.sort_unstable();
+    vs.dedup();
+
+    let in_box = |(x, y): Point| min.0 <= x && x <= max.0 && min.1 <= y && y <= max.1;
+    let corners = [min, (min.0, max.1), (max.0, min.1), max];
+    let crossings = us
+        .iter()
+        .flat_map(|&u| vs.iter().filter_map(move |&v| unrotate((u, v))));
+
+    corners
+        .into_iter()
+        .chain(crossings)
+        .filter(|&p| in_box(p))
+        .find(|&p| diamonds.iter().all(|d| !d.contains(p)))
+}
+
+/// The number of lattice points covered by at least one diamond.
+///
+/// In rotated coordinates the diamonds are squares, but only every other `(u, v)` is a lattice
+/// point. Both parities form a regular grid, so each is counted as a union of rectangles.
+///
+/// ```
+/// use aoc::geom::manhattan::{union_lattice_points, Diamond};
+///
+/// let a = Diamond::new((0, 0), 2);
+/// let b = Diamond::new((3, 0), 2);
+/// // 13 points each, (1, 0) and (2, 0) are in both
+/// assert_eq!(union_lattice_points(&[a, b]), 13 + 13 - 2);
+/// assert_eq!(union_lattice_points(&[a, a]), 13);
+/// assert_eq!(union_lattice_points(&[Diamond::new((0, 0), 1_000_000)]), 2_000_002_000_001);
+/// ```
+pub fn union_lattice_points(diamonds: &[Diamond]) -> i64 {
+    (0..2)
+        .map(|parity| {
+            // u = 2a + parity, v = 2b + parity
+            let rects: Vec<_> = diamonds
+                .iter()
+                .map(|d| d.rotated())
+                .map(|r| {
+                    // The a with min <= 2a + parity < max start at ceil((min - parity) / 2)
+                    let a = |x: i64| (x - parity + 1).div_euclid(2);
+                    Rect::new((a(r.min.0), a(r.min.1)), (a(r.max.0), a(r.max.1)))
+                })
+                .collect();
+            union_area(&rects)
+        })
+        .sum()
+}
@@ -0,0 +1,8 @@
+//! Exact integer geometry on the plane.
+//!
+//! Points are `(x, y)` tuples. Nothing here cares whether y grows up or down, except for the sign of
+//! orientation dependent results, which are documented for y pointing up.
+pub mod line;
+pub mod manhattan;
+pub mod polygon;
+pub mod rect;
@@ -0,0 +1,376 @@
+//! Simple polygons given by their vertices in order.
+//!
+//! Lattice polygons (integer vertices) come up in puzzles that trace a loop on a grid and ask how many
+//! cells it encloses. The shoelace formula gives the area, and Pick's theorem turns the area and the
+//! number of boundary points into the number of interior points:
+//!
+//! `area = interior + boundary / 2 - 1`
+//!
+//! Everything is exact. Intermediate products are of the order of `x * y`, so pick the coordinate
+//! type with that in mind.
+use num_traits::{PrimInt, Signed};
+
+use super::rect::Rect;
+use crate::math::{gcd, Rational};
+
+fn edges<T: Copy>(points: &[T]) -> impl Iterator<Item = (T, T)> + '_ {
+    let next = points.iter().cycle().skip(1);
+    points.iter().copied().zip(next.copied())
+}
+
+/// Twice the signed area of the polygon, which is always an integer. Positive if the vertices are in
+/// counter-clockwise order (with y pointing up), negative if they are clockwise.
+///
+/// ```
+/// use aoc::geom::polygon::twice_signed_area;
+///
+/// let triangle = [(0, 0), (3, 0), (0, 3)];
+/// assert_eq!(twice_signed_area(&triangle), 9);
+///
+/// let clockwise = [(0, 0), (0, 3), (3, 0)];
+/// assert_eq!(twice_signed_area(&clockwise), -9);
+/// ```
+pub fn twice_signed_area<T: PrimInt + Signed>(points: &[(T, T)]) -> T {
+    edges(points).fold(T::zero(), |sum, ((x1, y1), (x2, y2))| {
+        sum + x1 * y2 - x2 * y1
+    })
+}
+
+/// Twice the area of the polygon, see [`twice_signed_area`].
+pub fn twice_area<T: PrimInt + Signed>(points: &[(T, T)]) -> T {
+    twice_signed_area(points).abs()
+}
+
+/// The area of the polygon (shoelace formula). Lattice polygons can have a half integer area.
+///
+/// ```
+/// use aoc::geom::polygon::area;
+/// use aoc::math::Rational;
+///
+/// assert_eq!(area(&[(0, 0), (4, 0), (4, 3), (0, 3)]), Rational::from(12));
+/// assert_eq!(area(&[(0, 0), (3, 0), (0, 3)]), Rational::new(9, 2));
+/// ```
+pub fn area<T: PrimInt + Signed>(points: &[(T, T)]) -> Rational<T> {
+    Rational::new(twice_area(points), T::one() + T::one())
+}
+
+/// The number of lattice points on the edges of the polygon.
+///
+/// ```
+/// use aoc::geom::polygon::boundary_points;
+///
+/// assert_eq!(boundary_points(&[(0, 0), (4, 0), (4, 3), (0, 3)]), 14);
+/// // The diagonal only passes through (0, 3), (1, 2), (2, 1) and (3, 0)
+/// assert_eq!(boundary_points(&[(0, 0), (3, 0), (0, 3)]), 9);
+/// ```
+pub fn boundary_points<T: PrimInt + Signed>(points: &[(T, T)]) -> T {
+    edges(points).fold(T::zero(), |sum, ((x1, y1), (x2, y2))| {
+        sum + gcd(x2 - x1, y2 - y1)
+    })
+}
+
+/// The number of lattice points strictly inside the polygon (Pick's theorem).
+///
+/// ```
+/// use aoc::geom::polygon::interior_points;
+///
+/// assert_eq!(interior_points(&[(0, 0), (4, 0), (4, 3), (0, 3)]), 6);
+/// assert_eq!(interior_points(&[(0, 0), (3, 0), (0, 3)]), 1);
+/// ```
+pub fn interior_points<T: PrimInt + Signed>(points: &[(T, T)]) -> T {
+    let two = T::one() + T::one();
+    (twice_area(points) - boundary_points(points) + two) / two
+}
+
+/// The number of lattice points inside or on the polygon. When the polygon traces the centers of
+/// grid cells, this is the number of cells it covers.
+///
+/// ```
+/// use aoc::geom::polygon::lattice_points;
+///
+/// assert_eq!(lattice_points(&[(0, 0), (4, 0), (4, 3), (0, 3)]), 20);
+/// ```
+pub fn lattice_points<T: PrimInt + Signed>(points: &[(T, T)]) -> T {
+    interior_points(points) + boundary_points(points)
+}
+
+/// Builds a polygon by walking from `start`. Every step moves `len` times in direction `dir`.
+///
+/// Returns the corners visited. If the walk ends where it started, the end point is not repeated.
+///
+/// ```
+/// use aoc::geom::polygon::{from_walk, lattice_points};
+///
+/// let (right, down, left, up) = ((1, 0), (0, 1), (-1, 0), (0, -1));
+/// let walk = [(right, 6), (down, 5), (left, 2), (down, 2), (left, 4), (up, 7)];
+///
+/// let polygon = from_walk((0, 0), walk);
+/// assert_eq!(polygon, [(0, 0), (6, 0), (6, 5), (4, 5), (4, 7), (0, 7)]);
+/// assert_eq!(lattice_points(&polygon), 7 * 6 + 2 * 5);
+/// ```
+pub fn from_walk<T: PrimInt>(
+    start: (T, T),
+    steps: impl IntoIterator<Item = ((T, T), T)>,
+) -> Vec<(T, T)> {
+    let mut points = vec![start];
+    let mut point = start;
+    for ((dx, dy), len) in steps {
+        point = (point.0 + dx * len, point.1 + dy * len);
+        points.push(point);
+    }
+
+    if points.len() > 1 && points.last() == Some(&start) {
+        points.pop();
+    }
+    points
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl Location {
+    /// `true` for [`Location::Inside`] and [`Location::Boundary`].
+    pub fn is_covered(self) -> bool {
+        self != Location::Outside
+    }
+}
+
+/// Locates `p` relative to any simple polygon, exactly.
+///
+/// ```
+/// use aoc::geom::polygon::{locate, Location};
+///
+/// let diamond = [(2, 0), (4, 2), (2, 4), (0, 2)];
+/// assert_eq!(locate(&diamond, (2, 2)), Location::Inside);
+/// assert_eq!(locate(&diamond, (3, 1)), Location::Boundary);
+/// assert_eq!(locate(&diamond, (4, 2)), Location::Boundary);
+/// assert_eq!(locate(&diamond, (3, 3)), Location::Boundary);
+/// assert_eq!(locate(&diamond, (4, 4)), Location::Outside);
+/// // The ray from (0, 0) passes through the vertex (2, 0) and must not count it
+/// assert_eq!(locate(&diamond, (0, 0)), Location::Outside);
+/// ```
+pub fn locate<T: PrimInt + Signed>(points: &[(T, T)], (px, py): (T, T)) -> Location {
+    let mut inside = false;
+    for ((ax, ay), (bx, by)) in edges(points) {
+        let cross = (bx - ax) * (py - ay) - (px - ax) * (by - ay);
+        if cross.is_zero()
+            && ax.min(bx) <= px
+            && px <= ax.max(bx)
+            && ay.min(by) <= py
+            && py <= ay.max(by)
+        {
+            return Location::Boundary;
+        }
+
+        // Count the edges crossing the ray going right from p. Every edge includes its lower
+        // end point and excludes its upper one, so a vertex on the ray is counted once or twice
+        // as needed.
+        if (ay > py) != (by > py) {
+            let right_of_p = if by > ay {
+                cross > T::zero()
+            } else {
+                cross < T::zero()
+            };
+            inside ^= right_of_p;
+        }
+    }
+
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// `true` if `p` is inside or on the boundary of the polygon.
+pub fn contains<T: PrimInt + Signed>(points: &[(T, T)], p: (T, T)) -> bool {
+    locate(points, p).is_covered()
+}
+
+/// `RectilinearPolygon` is a polygon whose edges are all horizontal or vertical.
+///
+/// Those only need comparisons, so they also work with unsigned coordinates. The edges are sorted,
+/// which makes queries faster than [`locate`].
+///
+/// ```
+/// use aoc::geom::polygon::{Location, RectilinearPolygon};
+///
+/// // An L shape
+/// let polygon = RectilinearPolygon::new(vec![(0u64, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+/// assert_eq!(polygon.locate((1, 1)), Location::Inside);
+/// assert_eq!(polygon.locate((3, 2)), Location::Boundary);
+/// assert_eq!(polygon.locate((2, 3)), Location::Boundary);
+/// assert_eq!(polygon.locate((3, 3)), Location::Outside);
+/// assert_eq!(polygon.locate((1, 2)), Location::Inside);
+/// assert_eq!(polygon.locate((5, 0)), Location::Outside);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RectilinearPolygon<T> {
+    vertices: Vec<(T, T)>,
+    /// `(x, y_min, y_max)` sorted by x
+    vertical: Vec<(T, T, T)>,
+    /// `(y, x_min, x_max)` sorted by y
+    horizontal: Vec<(T, T, T)>,
+}
+
+impl<T: PrimInt> RectilinearPolygon<T> {
+    /// Creates the polygon from its corners in order. Panics if an edge is diagonal.
+    pub fn new(vertices: Vec<(T, T)>) -> Self {
+        let mut vertical = vec![];
+        let mut horizontal = vec![];
+        for ((x1, y1), (x2, y2)) in edges(&vertices) {
+            if x1 == x2 {
+                vertical.push((x1, y1.min(y2), y1.max(y2)));
+            } else if y1 == y2 {
+                horizontal.push((y1, x1.min(x2), x1.max(x2)));
+            } else {
+                panic!("edge is neither horizontal nor vertical");
+            }
+        }
+        vertical.sort_unstable();
+        horizontal.sort_unstable();
+
+        Self {
+            vertices,
+            vertical,
+            horizontal,
+        }
+    }
+
+    pub fn vertices(&self) -> &[(T, T)] {
+        &self.vertices
+    }
+
+    /// The vertical edges as `(x, y_min, y_max)`, sorted by x.
+    pub fn vertical_edges(&self) -> &[(T, T, T)] {
+        &self.vertical
+    }
+
+    /// The horizontal edges as `(y, x_min, x_max)`, sorted by y.
+    pub fn horizontal_edges(&self) -> &[(T, T, T)] {
+        &self.horizontal
+    }
+
+    pub fn locate(&self, p: (T, T)) -> Location {
+        self.locate_scaled(p, |v| v)
+    }
+
+    /// Like [`locate`](Self::locate), but `p` is in doubled coordinates. Lets the point sit halfway
+    /// between two lattice points.
+    fn locate_doubled(&self, p: (T, T)) -> Location {
+        self.locate_scaled(p, |v| v + v)
+    }
+
+    fn locate_scaled(&self, (px, py): (T, T), scale: impl Fn(T) -> T) -> Location {
+        let on = |edges: &[(T, T, T)], a: T, b: T| {
+            let start = edges.partition_point(|&(e, _, _)| scale(e) < a);
+            edges[start..]
+                .iter()
+                .take_while(|&&(e, _, _)| scale(e) == a)
+                .any(|&(_, lo, hi)| scale(lo) <= b && b <= scale(hi))
+        };
+        if on(&self.vertical, px, py) || on(&self.horizontal, py, px) {
+            return Location::Boundary;
+        }
+
+        // Cast a ray to the right. Edges include their lower end and exclude their upper one, so
+        // passing through a vertex is counted correctly.
+        let start = self.vertical.partition_point(|&(x, _, _)| scale(x) <= px);
+        let crossings = self.vertical[start..]
+            .iter()
+            .filter(|&&(_, lo, hi)| scale(lo) <= py && py < scale(hi))
+            .count();
+
+        if crossings % 2 == 1 {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// `true` if `p` is inside or on the boundary of the polygon.
+    pub fn contains(&self, p: (T, T)) -> bool {
+        self.locate(p).is_covered()
+    }
+
+    /// `true` if the whole rectangle is inside or on the boundary of the polygon. The rectangle is
+    /// taken as the closed box from `rect.min` to `rect.max_inclusive()`, so it has lattice points
+    /// for corners just like the polygon.
+    ///
+    /// ```
+    /// use aoc::geom::{polygon::RectilinearPolygon, rect::Rect};
+    ///
+    /// // A U shape, the gap between the arms is 1 wide
+    /// let u = RectilinearPolygon::new(vec![(0, 0), (5, 0), (5, 4), (3, 4), (3, 1), (2, 1), (2, 4), (0, 4)]);
+    /// assert!(u.contains_rect(&Rect::inclusive((0, 0), (5, 1))));
+    /// assert!(u.contains_rect(&Rect::inclusive((3, 1), (5, 4))));
+    /// assert!(!u.contains_rect(&Rect::inclusive((0, 0), (5, 2))));
+    ///
+    /// // Both arms touch the gap, but the gap itself is outside
+    /// assert!(u.contains_rect(&Rect::inclusive((2, 3), (2, 3))));
+    /// assert!(!u.contains_rect(&Rect::inclusive((2, 3), (3, 3))));
+    /// assert!(u.contains_rect(&Rect::inclusive((2, 1), (3, 1))));
+    /// ```
+    pub fn contains_rect(&self, rect: &Rect<T>) -> bool {
+        if rect.is_empty() {
+            return true;
+        }
+        let (x0, y0) = rect.min;
+        let (x1, y1) = rect.max_inclusive();
+
+        if x0 < x1 && y0 < y1 {
+            // If no edge cuts through the open interior, it is either all inside or all outside
+            let cuts = |edges: &[(T, T, T)], a0: T, a1: T, b0: T, b1: T| {
+                let start = edges.partition_point(|&(e, _, _)| e <= a0);
+                edges[start..]
+                    .iter()
+                    .take_while(|&&(e, _, _)| e < a1)
+                    .any(|&(_, lo, hi)| lo < b1 && b0 < hi)
+            };
+            if cuts(&self.vertical, x0, x1, y0, y1) || cuts(&self.horizontal, y0, y1, x0, x1) {
+                return false;
+            }
+            return self.locate_doubled((x0 + x1, y0 + y1)) == Location::Inside;
+        }
+
+        // A segment (or a single point) can only leave the polygon where it meets a perpendicular
+        // edge, so check those points and every point halfway between them
+        let mut xs = vec![x0, x1];
+        let mut ys = vec![y0, y1];
+        if y0 == y1 {
+            xs.extend(
+                self.vertical
+                    .iter()
+                    .map(|e| e.0)
+                    .filter(|&x| x0 < x && x < x1),
+            );
+        }
+        if x0 == x1 {
+            ys.extend(
+                self.horizontal
+                    .iter()
+                    .map(|e| e.0)
+                    .filter(|&y| y0 < y && y < y1),
+            );
+        }
+
+        let doubled = |mut values: Vec<T>| {
+            values.sort_unstable();
+            values.dedup();
+            let halfway = values.windows(2).map(|w| w[0] + w[1]).collect::<Vec<_>>();
+            values
+                .iter()
+                .map(|&v| v + v)
+                .chain(halfway)
+                .collect::<Vec<_>>()
+        };
+        let (xs, ys) = (doubled(xs), doubled(ys));
+        xs.iter()
+            .all(|&x| ys.iter().all(|&y| self.locate_doubled((x, y)).is_covered()))
+    }
+}
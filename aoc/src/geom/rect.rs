@@ -0,0 +1,280 @@
+//! Axis-aligned integer rectangles.
+//!
+//! A [`Rect`] is half-open, it covers `min.0..max.0` by `min.1..max.1`. That makes areas,
+//! intersections and differences simple. Rectangles given by the two grid cells in opposite corners
+//! are built with [`Rect::inclusive`].
+use num_traits::PrimInt;
+
+/// `Rect` is the half-open rectangle `[min.0, max.0) x [min.1, max.1)`.
+///
+/// ```
+/// use aoc::geom::rect::Rect;
+///
+/// // The cells (2, 1) to (4, 3), both included
+/// let a = Rect::inclusive((4, 1), (2, 3));
+/// assert_eq!(a, Rect::new((2, 1), (5, 4)));
+/// assert_eq!((a.width(), a.height(), a.area()), (3, 3, 9));
+/// assert!(a.contains((4, 3)));
+/// assert!(!a.contains((5, 3)));
+///
+/// let b = Rect::new((3, 0), (10, 2));
+/// assert_eq!(a.intersection(&b), Some(Rect::new((3, 1), (5, 2))));
+/// assert_eq!(a.bounding(&b), Rect::new((2, 0), (10, 4)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: (T, T),
+    pub max: (T, T),
+}
+
+impl<T: PrimInt> Rect<T> {
+    /// Creates the rectangle `[min.0, max.0) x [min.1, max.1)`. Panics if `max` is below or left of
+    /// `min`.
+    pub fn new(min: (T, T), max: (T, T)) -> Self {
+        assert!(min.0 <= max.0 && min.1 <= max.1, "max is smaller than min");
+        Self { min, max }
+    }
+
+    /// Creates the rectangle with corners `a` and `b`, in any order, excluding the right and bottom
+    /// edge.
+    pub fn exclusive(a: (T, T), b: (T, T)) -> Self {
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1)),
+            max: (a.0.max(b.0), a.1.max(b.1)),
+        }
+    }
+
+    /// Creates the smallest rectangle covering the cells `a` and `b`, which are opposite corners in
+    /// any order.
+    pub fn inclusive(a: (T, T), b: (T, T)) -> Self {
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1)),
+            max: (a.0.max(b.0) + T::one(), a.1.max(b.1) + T::one()),
+        }
+    }
+
+    /// The last cell covered by the rectangle. Meaningless for empty rectangles.
+    pub fn max_inclusive(&self) -> (T, T) {
+        (self.max.0 - T::one(), self.max.1 - T::one())
+    }
+
+    pub fn width(&self) -> T {
+        self.max.0 - self.min.0
+    }
+
+    pub fn height(&self) -> T {
+        self.max.1 - self.min.1
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.0 == self.max.0 || self.min.1 == self.max.1
+    }
+
+    pub fn contains(&self, (x, y): (T, T)) -> bool {
+        self.min.0 <= x && x < self.max.0 && self.min.1 <= y && y < self.max.1
+    }
+
+    /// `true` if every cell of `other` is also in `self`.
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (self.min.0 <= other.min.0
+                && other.max.0 <= self.max.0
+                && self.min.1 <= other.min.1
+                && other.max.1 <= self.max.1)
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The cells in both rectangles, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = (self.min.0.max(other.min.0), self.min.1.max(other.min.1));
+        let max = (self.max.0.min(other.max.0), self.max.1.min(other.max.1));
+        (min.0 < max.0 && min.1 < max.1).then_some(Self { min, max })
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn bounding(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Self {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// The cells of `self` that are not in `other`, as at most four disjoint rectangles.
+    ///
+    /// ```
+    /// use aoc::geom::rect::Rect;
+    ///
+    /// let a = Rect::new((0, 0), (4, 4));
+    /// let hole = Rect::new((1, 1), (3, 3));
+    /// let pieces = a.difference(&hole);
+    /// assert_eq!(pieces.len(), 4);
+    /// assert_eq!(pieces.iter().map(Rect::area).sum::<i32>(), 16 - 4);
+    ///
+    /// assert_eq!(a.difference(&Rect::new((2, -5), (9, 9))), [Rect::new((0, 0), (2, 4))]);
+    /// assert!(a.difference(&a).is_empty());
+    /// ```
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        // Full width bands above and below the cut, then the parts left and right of it
+        let pieces = [
+            Self::new(self.min, (self.max.0, cut.min.1)),
+            Self::new((self.min.0, cut.max.1), self.max),
+            Self::new((self.min.0, cut.min.1), (cut.min.0, cut.max.1)),
+            Self::new((cut.max.0, cut.min.1), (self.max.0, cut.max.1)),
+        ];
+        pieces.into_iter().filter(|r| !r.is_empty()).collect()
+    }
+
+    /// The cells in either rectangle, as disjoint rectangles.
+    ///
+    /// ```
+    /// use aoc::geom::rect::Rect;
+    ///
+    /// let a = Rect::new((0, 0), (3, 3));
+    /// let b = Rect::new((2, 2), (5, 5));
+    /// assert_eq!(a.union(&b).iter().map(Rect::area).sum::<i32>(), 9 + 9 - 1);
+    /// ```
+    pub fn union(&self, other: &Self) -> Vec<Self> {
+        let mut pieces = other.difference(self);
+        if !self.is_empty() {
+            pieces.insert(0, *self);
+        }
+        pieces
+    }
+}
+
+/// The number of cells covered by at least one of the rectangles.
+///
+/// Sweeps a line over the x coordinates, keeping track of how often each compressed y interval is
+/// covered. Runs in `O(n^2)` for `n` rectangles, independent of their size.
+///
+/// ```
+/// use aoc::geom::rect::{union_area, Rect};
+///
+/// let rects = [
+///     Rect::new((0, 0), (4, 4)),
+///     Rect::new((2, 2), (6, 6)),
+///     Rect::new((1_000_000_000, 0), (2_000_000_000, 3)),
+/// ];
+/// assert_eq!(union_area(&rects), 16 + 16 - 4 + 3_000_000_000i64);
+/// ```
+pub fn union_area<T: PrimInt>(rects: &[Rect<T>]) -> T {
+    let rects: Vec<_> = rects.iter().filter(|r| !r.is_empty()).collect();
+    let ys = Compression::new(rects.iter().flat_map(|r| [r.min.1, r.max.1]));
+
+    // (x, start or end, y range)
+    let mut events: Vec<_> = rects
+        .iter()
+        .flat_map(|r| {
+            let span = (ys.index(r.min.1).unwrap(), ys.index(r.max.1).unwrap());
+            [(r.min.0, true, span), (r.max.0, false, span)]
+        })
+        .collect();
+    events.sort_unstable_by_key(|&(x, _, _)| x);
+
+    let mut coverage = vec![0usize; ys.len().saturating_sub(1)];
+    let mut covered = T::zero();
+    let mut area = T::zero();
+    let mut last_x = T::zero();
+    for (x, start, (lo, hi)) in events {
+        area = area + covered * (x - last_x);
+        last_x = x;
+
+        for (i, count) in coverage.iter_mut().enumerate().take(hi).skip(lo) {
+            let before = *count;
+            if start {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+            match (before, *count) {
+                (0, _) => covered = covered + ys.span(i),
+                (_, 0) => covered = covered - ys.span(i),
+                _ => {}
+            }
+        }
+    }
+    area
+}
+
+/// `Compression` maps a sparse set of coordinates to the dense indices `0..len`, keeping their
+/// order.
+///
+/// Puzzles with huge coordinates often only care about a few hundred distinct values. After
+/// compressing them, index `i` stands for the interval `value(i)..value(i + 1)`, which is [`span`]
+/// wide. Include `v + 1` next to `v` when a single cell at `v` has to stay separate from its
+/// neighbours.
+///
+/// [`span`]: Compression::span
+///
+/// ```
+/// use aoc::geom::rect::Compression;
+///
+/// let xs = Compression::new([1_000_000, 5, 70, 5]);
+/// assert_eq!(xs.values(), [5, 70, 1_000_000]);
+/// assert_eq!(xs.index(70), Some(1));
+/// assert_eq!(xs.index(71), None);
+/// assert_eq!(xs.value(2), 1_000_000);
+/// assert_eq!(xs.span(1), 999_930);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compression<T> {
+    values: Vec<T>,
+}
+
+impl<T: PrimInt> Compression<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// The index of `value`, if it was one of the compressed values.
+    pub fn index(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// The index of the largest value that is not above `value`, if any. This is the interval that
+    /// contains `value`.
+    pub fn floor_index(&self, value: T) -> Option<usize> {
+        self.values.partition_point(|&v| v <= value).checked_sub(1)
+    }
+
+    pub fn value(&self, index: usize) -> T {
+        self.values[index]
+    }
+
+    /// The distance from `value(index)` to `value(index + 1)`.
+    pub fn span(&self, index: usize) -> T {
+        self.values[index + 1] - self.values[index]
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
@@ -0,0 +1,282 @@
+//! Cliques in undirected graphs.
+//!
+//! Adjacency is converted to one bit set per node, so intersecting neighbourhoods is cheap for graphs
+//! of up to a few thousand nodes. All results are sorted, so they can be compared or printed directly.
+
+/// A growable bit set, one row of the adjacency matrix.
+#[derive(Clone)]
+struct Row(Vec<u64>);
+
+impl Row {
+    fn new(n: usize) -> Self {
+        Self(vec![0; n.div_ceil(64)])
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+
+    fn and(&self, other: &Row) -> Row {
+        Row(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn and_not(&self, other: &Row) -> Row {
+        Row(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn or(&self, other: &Row) -> Row {
+        Row(self.0.iter().zip(&other.0).map(|(a, b)| a | b).collect())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn and_len(&self, other: &Row) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+/// Builds the full and the "forward" (only larger neighbours) adjacency rows. Self loops are ignored.
+fn rows(adj: &[Vec<usize>]) -> (Vec<Row>, Vec<Row>) {
+    let n = adj.len();
+    let mut full = vec![Row::new(n); n];
+    let mut forward = vec![Row::new(n); n];
+
+    for (u, neighbors) in adj.iter().enumerate() {
+        for &v in neighbors.iter().filter(|&&v| v != u) {
+            full[u].insert(v);
+            full[v].insert(u);
+            let (a, b) = (u.min(v), u.max(v));
+            forward[a].insert(b);
+        }
+    }
+
+    (full, forward)
+}
+
+/// `triangles` returns every triangle of an undirected graph as `[a, b, c]` with `a < b < c`. The
+/// list is sorted.
+///
+/// ```
+/// use aoc::graph::{triangles, NamedGraph};
+///
+/// let graph: NamedGraph = [
+///     ("kh", "tc"), ("qp", "kh"), ("de", "cg"), ("ka", "co"), ("yn", "aq"), ("qp", "ub"),
+///     ("cg", "tb"), ("vc", "aq"), ("tb", "ka"), ("wh", "tc"), ("yn", "cg"), ("kh", "ub"),
+///     ("ta", "co"), ("de", "co"), ("tc", "td"), ("tb", "wq"), ("wh", "td"), ("ta", "ka"),
+///     ("td", "qp"), ("aq", "cg"), ("wq", "ub"), ("ub", "vc"), ("de", "ta"), ("wq", "aq"),
+///     ("wq", "vc"), ("wh", "yn"), ("ka", "de"), ("kh", "ta"), ("co", "tc"), ("wh", "qp"),
+///     ("tb", "vc"), ("td", "yn"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let all = triangles(graph.adj());
+/// assert_eq!(all.len(), 12);
+///
+/// let with_t = all
+///     .iter()
+///     .filter(|t| t.iter().any(|&i| graph.name(i).starts_with('t')))
+///     .count();
+/// assert_eq!(with_t, 7);
+/// ```
+pub fn triangles(adj: &[Vec<usize>]) -> Vec<[usize; 3]> {
+    let (_, forward) = rows(adj);
+
+    let mut triangles = vec![];
+    for (a, row) in forward.iter().enumerate() {
+        for b in row.iter() {
+            for c in row.and(&forward[b]).iter() {
+                triangles.push([a, b, c]);
+            }
+        }
+    }
+    triangles
+}
+
+/// `count_cliques` returns the number of cliques with exactly `k` nodes.
+///
+/// ```
+/// use aoc::graph::count_cliques;
+///
+/// // A complete graph on 5 nodes has (5 choose k) cliques of size k
+/// let adj: Vec<Vec<usize>> = (0..5).map(|i| (0..5).filter(|&j| j != i).collect()).collect();
+///
+/// assert_eq!(count_cliques(&adj, 1), 5);
+/// assert_eq!(count_cliques(&adj, 3), 10);
+/// assert_eq!(count_cliques(&adj, 5), 1);
+/// assert_eq!(count_cliques(&adj, 6), 0);
+/// ```
+pub fn count_cliques(adj: &[Vec<usize>], k: usize) -> usize {
+    fn count(k: usize, candidates: &Row, forward: &[Row]) -> usize {
+        match k {
+            0 => 1,
+            1 => candidates.len(),
+            _ => candidates
+                .iter()
+                .map(|v| count(k - 1, &candidates.and(&forward[v]), forward))
+                .sum(),
+        }
+    }
+
+    if k == 0 {
+        return 1;
+    }
+
+    let (_, forward) = rows(adj);
+    (0..adj.len())
+        .map(|v| count(k - 1, &forward[v], &forward))
+        .sum()
+}
+
+/// Bron-Kerbosch with pivoting. Calls `found` with every maximal clique, `prune` is given the size of
+/// the clique so far plus the number of remaining candidates and may skip the branch.
+fn bron_kerbosch(
+    clique: &mut Vec<usize>,
+    candidates: Row,
+    mut excluded: Row,
+    full: &[Row],
+    prune: &dyn Fn(usize) -> bool,
+    found: &mut dyn FnMut(&[usize]),
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            found(clique);
+        }
+        return;
+    }
+    if prune(clique.len() + candidates.len()) {
+        return;
+    }
+
+    // Pick the pivot that leaves the fewest branches to explore
+    let pivot = candidates
+        .or(&excluded)
+        .iter()
+        .max_by_key(|&u| candidates.and_len(&full[u]))
+        .unwrap();
+
+    let mut candidates = candidates;
+    for v in candidates.and_not(&full[pivot]).iter() {
+        clique.push(v);
+        bron_kerbosch(
+            clique,
+            candidates.and(&full[v]),
+            excluded.and(&full[v]),
+            full,
+            prune,
+            found,
+        );
+        clique.pop();
+
+        candidates.remove(v);
+        excluded.insert(v);
+    }
+}
+
+/// `maximal_cliques` returns every clique that cannot be extended by another node. Each clique is
+/// sorted, and the list of cliques is sorted.
+///
+/// ```
+/// use aoc::graph::maximal_cliques;
+///
+/// // A square 0 - 1 - 2 - 3 - 0 with the diagonal 0 - 2, and an isolated node 4
+/// let adj = vec![vec![1, 2, 3], vec![0, 2], vec![0, 1, 3], vec![0, 2], vec![]];
+///
+/// assert_eq!(maximal_cliques(&adj), vec![vec![0, 1, 2], vec![0, 2, 3], vec![4]]);
+/// ```
+pub fn maximal_cliques(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = adj.len();
+    let (full, _) = rows(adj);
+
+    let mut all = Row::new(n);
+    (0..n).for_each(|i| all.insert(i));
+
+    let mut cliques = vec![];
+    bron_kerbosch(
+        &mut vec![],
+        all,
+        Row::new(n),
+        &full,
+        &|_| false,
+        &mut |clique| {
+            let mut clique = clique.to_vec();
+            clique.sort_unstable();
+            cliques.push(clique);
+        },
+    );
+
+    cliques.sort_unstable();
+    cliques
+}
+
+/// `maximum_clique` returns the largest clique, sorted. If several cliques have the largest size the
+/// lexicographically smallest one is returned.
+///
+/// ```
+/// use aoc::graph::{maximum_clique, NamedGraph};
+///
+/// let graph: NamedGraph = [
+///     ("ka", "co"), ("ta", "co"), ("de", "co"), ("ta", "ka"), ("de", "ta"), ("ka", "de"),
+///     ("kh", "tc"), ("qp", "kh"), ("wh", "tc"), ("co", "tc"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let clique = maximum_clique(graph.adj());
+/// assert_eq!(graph.sorted_names(clique).join(","), "co,de,ka,ta");
+/// ```
+pub fn maximum_clique(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let (full, _) = rows(adj);
+
+    let mut all = Row::new(n);
+    (0..n).for_each(|i| all.insert(i));
+
+    let best_len = std::cell::Cell::new(0);
+    let mut best: Vec<usize> = vec![];
+    bron_kerbosch(
+        &mut vec![],
+        all,
+        Row::new(n),
+        &full,
+        &|bound| bound < best_len.get(),
+        &mut |clique| {
+            let mut clique = clique.to_vec();
+            clique.sort_unstable();
+            if clique.len() > best.len() || (clique.len() == best.len() && clique < best) {
+                best_len.set(clique.len());
+                best = clique;
+            }
+        },
+    );
+
+    best
+}
@@ -0,0 +1,329 @@
+//! Minimum cuts and maximum flows.
+//!
+//! Undirected graphs are given as symmetric adjacency lists: if `v` is in `adj[u]` then `u` is in
+//! `adj[v]`. Every edge has a capacity of 1, parallel edges add up.
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::UnionFind;
+
+/// `Cut` splits the nodes of a graph into two sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    /// The total capacity of the edges crossing the cut.
+    pub weight: u64,
+    /// The nodes on one side of the cut, sorted. The other side is every other node.
+    pub side: Vec<usize>,
+    /// The edges crossing the cut as `(inside, outside)` pairs, sorted.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl Cut {
+    fn new(weight: u64, mut side: Vec<usize>, adj: &[Vec<usize>]) -> Self {
+        side.sort_unstable();
+
+        let mut inside = vec![false; adj.len()];
+        for &node in &side {
+            inside[node] = true;
+        }
+
+        let mut edges: Vec<(usize, usize)> = side
+            .iter()
+            .flat_map(|&u| adj[u].iter().map(move |&v| (u, v)))
+            .filter(|&(_, v)| !inside[v])
+            .collect();
+        edges.sort_unstable();
+
+        Self {
+            weight,
+            side,
+            edges,
+        }
+    }
+}
+
+/// Stoer-Wagner global minimum cut of an undirected graph.
+///
+/// Finds the smallest set of edges whose removal disconnects the graph, without needing to know
+/// which nodes end up on either side. Returns `None` if the graph has fewer than 2 nodes.
+///
+/// Runs in O(V * E log V) time.
+///
+/// ```
+/// use aoc::graph::global_min_cut;
+///
+/// // Two triangles {0, 1, 2} and {3, 4, 5} joined by the edge 2 - 3
+/// let adj = vec![
+///     vec![1, 2],
+///     vec![0, 2],
+///     vec![0, 1, 3],
+///     vec![2, 4, 5],
+///     vec![3, 5],
+///     vec![3, 4],
+/// ];
+///
+/// let cut = global_min_cut(&adj).unwrap();
+/// assert_eq!(cut.weight, 1);
+/// assert_eq!(cut.side.len(), 3);
+/// assert!(cut.edges == vec![(2, 3)] || cut.edges == vec![(3, 2)]);
+/// ```
+pub fn global_min_cut(adj: &[Vec<usize>]) -> Option<Cut> {
+    let n = adj.len();
+    if n < 2 {
+        return None;
+    }
+
+    // Weighted adjacency between merged super nodes
+    let mut weights: Vec<HashMap<usize, u64>> = vec![HashMap::new(); n];
+    for (u, neighbors) in adj.iter().enumerate() {
+        for &v in neighbors {
+            if u != v {
+                *weights[u].entry(v).or_default() += 1;
+            }
+        }
+    }
+
+    let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut active = vec![true; n];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    for _ in 1..n {
+        // Maximum adjacency ordering: repeatedly add the node most tightly connected to the set
+        let mut key = vec![0u64; n];
+        let mut added = vec![false; n];
+        let mut heap: BinaryHeap<(u64, usize)> =
+            (0..n).filter(|&i| active[i]).map(|i| (0, i)).collect();
+
+        let (mut prev, mut last) = (usize::MAX, usize::MAX);
+        while let Some((k, node)) = heap.pop() {
+            if added[node] || k != key[node] {
+                continue;
+            }
+            added[node] = true;
+            (prev, last) = (last, node);
+
+            for (&next, &w) in &weights[node] {
+                if active[next] && !added[next] {
+                    key[next] += w;
+                    heap.push((key[next], next));
+                }
+            }
+        }
+
+        // The cut separating `last` from everything else is a candidate
+        if best.as_ref().is_none_or(|(w, _)| key[last] < *w) {
+            best = Some((key[last], members[last].clone()));
+        }
+
+        // Merge `last` into `prev`
+        let moved = std::mem::take(&mut members[last]);
+        members[prev].extend(moved);
+        for (next, w) in std::mem::take(&mut weights[last]) {
+            weights[next].remove(&last);
+            if next != prev {
+                *weights[prev].entry(next).or_default() += w;
+                *weights[next].entry(prev).or_default() += w;
+            }
+        }
+        active[last] = false;
+    }
+
+    best.map(|(weight, side)| Cut::new(weight, side, adj))
+}
+
+/// `FlowNetwork` is a directed graph with edge capacities for computing maximum flows.
+///
+/// ```
+/// use aoc::graph::FlowNetwork;
+///
+/// let mut network = FlowNetwork::new(4);
+/// network.add_edge(0, 1, 10);
+/// network.add_edge(0, 2, 10);
+/// network.add_edge(1, 2, 5);
+/// network.add_edge(1, 3, 2);
+/// network.add_edge(2, 3, 3);
+///
+/// assert_eq!(network.max_flow(0, 3), 5);
+///
+/// let cut = network.min_cut(0, 3);
+/// assert_eq!(cut.weight, 5);
+/// assert_eq!(cut.side, vec![0, 1, 2]);
+/// assert_eq!(cut.edges, vec![(1, 3), (2, 3)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlowNetwork {
+    // Edges are stored in pairs, edge `e ^ 1` is the reverse of edge `e`
+    edges: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    capacity: Vec<u64>,
+}
+
+impl FlowNetwork {
+    /// Creates a network with `n` nodes and no edges.
+    pub fn new(n: usize) -> Self {
+        Self {
+            edges: vec![vec![]; n],
+            to: vec![],
+            capacity: vec![],
+        }
+    }
+
+    /// Creates a network from an undirected adjacency list, every edge has capacity 1.
+    pub fn from_undirected(adj: &[Vec<usize>]) -> Self {
+        let mut network = Self::new(adj.len());
+        for (u, neighbors) in adj.iter().enumerate() {
+            for &v in neighbors.iter().filter(|&&v| u < v) {
+                network.add_undirected_edge(u, v, 1);
+            }
+        }
+        network
+    }
+
+    /// `len` returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// `is_empty` returns true if the network has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// `add_edge` adds a directed edge from `u` to `v`.
+    pub fn add_edge(&mut self, u: usize, v: usize, capacity: u64) {
+        self.add_pair(u, v, capacity, 0);
+    }
+
+    /// `add_undirected_edge` adds an edge that can carry `capacity` in either direction.
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, capacity: u64) {
+        self.add_pair(u, v, capacity, capacity);
+    }
+
+    fn add_pair(&mut self, u: usize, v: usize, forward: u64, backward: u64) {
+        let e = self.to.len();
+        self.edges[u].push(e);
+        self.to.push(v);
+        self.capacity.push(forward);
+        self.edges[v].push(e + 1);
+        self.to.push(u);
+        self.capacity.push(backward);
+    }
+
+    /// Edmonds-Karp: pushes flow along shortest augmenting paths. Returns the final residual
+    /// capacities and the total flow.
+    fn residual(&self, s: usize, t: usize) -> (Vec<u64>, u64) {
+        let mut residual = self.capacity.clone();
+        let mut flow = 0;
+
+        if s == t {
+            return (residual, flow);
+        }
+
+        loop {
+            // BFS for the shortest augmenting path, remembering the edge used to reach each node
+            let mut via = vec![usize::MAX; self.len()];
+            let mut queue = VecDeque::from([s]);
+            while let Some(u) = queue.pop_front() {
+                for &e in &self.edges[u] {
+                    let v = self.to[e];
+                    if residual[e] > 0 && v != s && via[v] == usize::MAX {
+                        via[v] = e;
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            if via[t] == usize::MAX {
+                return (residual, flow);
+            }
+
+            let mut bottleneck = u64::MAX;
+            let mut v = t;
+            while v != s {
+                bottleneck = bottleneck.min(residual[via[v]]);
+                v = self.to[via[v] ^ 1];
+            }
+
+            let mut v = t;
+            while v != s {
+                residual[via[v]] -= bottleneck;
+                residual[via[v] ^ 1] += bottleneck;
+                v = self.to[via[v] ^ 1];
+            }
+
+            flow += bottleneck;
+        }
+    }
+
+    /// `max_flow` returns the maximum flow from `s` to `t`.
+    pub fn max_flow(&self, s: usize, t: usize) -> u64 {
+        self.residual(s, t).1
+    }
+
+    /// `min_cut` returns a minimum `s`-`t` cut. The side of the cut contains `s`, and its weight is
+    /// the maximum flow.
+    pub fn min_cut(&self, s: usize, t: usize) -> Cut {
+        let (residual, flow) = self.residual(s, t);
+
+        // Everything still reachable from `s` is on the source side
+        let mut inside = vec![false; self.len()];
+        inside[s] = true;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for &e in &self.edges[u] {
+                let v = self.to[e];
+                if residual[e] > 0 && !inside[v] {
+                    inside[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        let side: Vec<usize> = (0..self.len()).filter(|&i| inside[i]).collect();
+        let mut edges: Vec<(usize, usize)> = (0..self.to.len())
+            .filter(|&e| self.capacity[e] > 0)
+            .map(|e| (self.to[e ^ 1], self.to[e]))
+            .filter(|&(u, v)| inside[u] && !inside[v])
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        Cut {
+            weight: flow,
+            side,
+            edges,
+        }
+    }
+}
+
+/// `component_sizes` returns the sizes of the connected components of an undirected graph after
+/// removing some of its edges, largest first.
+///
+/// Removed edges may be given in either direction.
+///
+/// ```
+/// use aoc::graph::component_sizes;
+///
+/// let adj = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+///
+/// assert_eq!(component_sizes(&adj, &[]), vec![4]);
+/// assert_eq!(component_sizes(&adj, &[(2, 1)]), vec![2, 2]);
+/// assert_eq!(component_sizes(&adj, &[(0, 1), (2, 3)]), vec![2, 1, 1]);
+/// ```
+pub fn component_sizes(adj: &[Vec<usize>], removed: &[(usize, usize)]) -> Vec<usize> {
+    let is_removed = |u: usize, v: usize| {
+        removed
+            .iter()
+            .any(|&(a, b)| (a, b) == (u, v) || (a, b) == (v, u))
+    };
+
+    let mut uf = UnionFind::new(adj.len());
+    for (u, neighbors) in adj.iter().enumerate() {
+        for &v in neighbors {
+            if !is_removed(u, v) {
+                uf.union(u, v);
+            }
+        }
+    }
+
+    uf.component_sizes()
+}
@@ -0,0 +1,15 @@
+//! Algorithms over graphs.
+//!
+//! Graphs are given as adjacency lists over dense indices: `adj[i]` lists the nodes that `i` has an
+//! edge to. Puzzles with named nodes can use [`NamedGraph`] to map the names to indices.
+mod clique;
+mod cut;
+mod named;
+mod order;
+mod scc;
+
+pub use clique::{count_cliques, maximal_cliques, maximum_clique, triangles};
+pub use cut::{component_sizes, global_min_cut, Cut, FlowNetwork};
+pub use named::NamedGraph;
+pub use order::{find_cycle, is_acyclic, toposort, CycleError, Precedence};
+pub use scc::strongly_connected_components;
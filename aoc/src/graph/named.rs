@@ -0,0 +1,106 @@
+//! Graphs whose nodes are identified by name.
+use crate::intern::Interner;
+
+/// `NamedGraph` maps node names to dense indices and stores an adjacency list over those indices,
+/// so that the index based algorithms in this module can be used on puzzle inputs directly.
+///
+/// Names are borrowed from the input, nothing is copied.
+///
+/// ```
+/// use aoc::graph::NamedGraph;
+///
+/// let graph: NamedGraph = [("jqt", "rhn"), ("jqt", "xhk"), ("rhn", "xhk")]
+///     .into_iter()
+///     .collect();
+///
+/// assert_eq!(graph.len(), 3);
+/// let rhn = graph.index("rhn").unwrap();
+/// assert_eq!(graph.name(rhn), "rhn");
+/// assert_eq!(graph.neighbors(rhn).len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NamedGraph<'a> {
+    names: Interner<'a>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl<'a> NamedGraph<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `add_node` returns the index of `name`, adding it to the graph if it is new.
+    pub fn add_node(&mut self, name: &'a str) -> usize {
+        let i = self.names.intern(name).index();
+        if i == self.adj.len() {
+            self.adj.push(vec![]);
+        }
+        i
+    }
+
+    /// `add_edge` adds an undirected edge between `a` and `b`, adding the nodes if needed.
+    pub fn add_edge(&mut self, a: &'a str, b: &'a str) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adj[a].push(b);
+        self.adj[b].push(a);
+    }
+
+    /// `add_arc` adds a directed edge from `a` to `b`, adding the nodes if needed.
+    pub fn add_arc(&mut self, a: &'a str, b: &'a str) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adj[a].push(b);
+    }
+
+    /// `index` returns the index of the node called `name`.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.get(name).map(|symbol| symbol.index())
+    }
+
+    /// `name` returns the name of the node at index `i`.
+    pub fn name(&self, i: usize) -> &'a str {
+        self.names.names()[i]
+    }
+
+    /// `names` returns the names of all nodes, in index order.
+    pub fn names(&self) -> &[&'a str] {
+        self.names.names()
+    }
+
+    /// `sorted_names` returns the names of the given nodes in alphabetical order.
+    pub fn sorted_names(&self, nodes: impl IntoIterator<Item = usize>) -> Vec<&'a str> {
+        let mut names: Vec<&'a str> = nodes.into_iter().map(|i| self.name(i)).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// `neighbors` returns the indices of the nodes that `i` has an edge to.
+    pub fn neighbors(&self, i: usize) -> &[usize] {
+        &self.adj[i]
+    }
+
+    /// `adj` returns the adjacency list, for use with the other algorithms in [`crate::graph`].
+    pub fn adj(&self) -> &[Vec<usize>] {
+        &self.adj
+    }
+
+    /// `len` returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// `is_empty` returns true if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for NamedGraph<'a> {
+    /// Builds an undirected graph from a list of edges.
+    fn from_iter<I: IntoIterator<Item = (&'a str, &'a str)>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        for (a, b) in iter {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
@@ -0,0 +1,245 @@
+//! Topological ordering and cycle detection.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+/// `CycleError` is returned when an ordering is requested for a graph that is not acyclic.
+///
+/// `cycle` lists the nodes of one offending cycle in order, the last node has an edge back to the
+/// first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<T> {
+    pub cycle: Vec<T>,
+}
+
+impl<T: fmt::Debug> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle: ")?;
+        for node in &self.cycle {
+            write!(f, "{:?} -> ", node)?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for CycleError<T> {}
+
+/// Kahn's algorithm. Returns the nodes in an order where every edge points forwards.
+///
+/// When several nodes are ready at the same time the smallest index goes first, so the result is
+/// the lexicographically smallest topological order.
+///
+/// ```
+/// use aoc::graph::{toposort, CycleError};
+///
+/// // 3 -> 1 -> 0, 2 -> 0
+/// let adj = vec![vec![], vec![0], vec![0], vec![1]];
+/// assert_eq!(toposort(&adj), Ok(vec![2, 3, 1, 0]));
+///
+/// // 0 -> 1 -> 2 -> 1
+/// let adj = vec![vec![1], vec![2], vec![1]];
+/// assert_eq!(toposort(&adj), Err(CycleError { cycle: vec![1, 2] }));
+/// ```
+pub fn toposort(adj: &[Vec<usize>]) -> Result<Vec<usize>, CycleError<usize>> {
+    let mut indegree = vec![0; adj.len()];
+    for &next in adj.iter().flatten() {
+        indegree[next] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..adj.len())
+        .filter(|&i| indegree[i] == 0)
+        .map(Reverse)
+        .collect();
+
+    let mut order = Vec::with_capacity(adj.len());
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(node);
+        for &next in &adj[node] {
+            indegree[next] -= 1;
+            if indegree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if order.len() == adj.len() {
+        Ok(order)
+    } else {
+        let cycle = find_cycle(adj).expect("kahn's algorithm stalled without a cycle");
+        Err(CycleError { cycle })
+    }
+}
+
+/// Finds a directed cycle with a depth first search.
+///
+/// Returns the nodes of the cycle in order, the last node has an edge back to the first one. A
+/// self loop is reported as a cycle of length 1.
+///
+/// ```
+/// use aoc::graph::find_cycle;
+///
+/// let adj = vec![vec![1], vec![2, 3], vec![], vec![1]];
+/// assert_eq!(find_cycle(&adj), Some(vec![1, 3]));
+///
+/// let adj = vec![vec![1], vec![2], vec![]];
+/// assert_eq!(find_cycle(&adj), None);
+/// ```
+pub fn find_cycle(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Color {
+        White,
+        Gray,
+        Black,
+    }
+
+    let mut color = vec![Color::White; adj.len()];
+    // The call stack of the search: (node, index of the next edge to follow)
+    let mut stack: Vec<(usize, usize)> = vec![];
+
+    for root in 0..adj.len() {
+        if color[root] != Color::White {
+            continue;
+        }
+
+        color[root] = Color::Gray;
+        stack.push((root, 0));
+
+        while let Some(top) = stack.last_mut() {
+            let node = top.0;
+            let Some(&next) = adj[node].get(top.1) else {
+                color[node] = Color::Black;
+                stack.pop();
+                continue;
+            };
+            top.1 += 1;
+
+            match color[next] {
+                Color::White => {
+                    color[next] = Color::Gray;
+                    stack.push((next, 0));
+                }
+                // Gray nodes are exactly the ones on the stack
+                Color::Gray => {
+                    let start = stack.iter().position(|&(n, _)| n == next).unwrap();
+                    return Some(stack[start..].iter().map(|&(n, _)| n).collect());
+                }
+                Color::Black => {}
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns true if the graph has no directed cycles.
+pub fn is_acyclic(adj: &[Vec<usize>]) -> bool {
+    find_cycle(adj).is_none()
+}
+
+/// `Precedence` is a set of "`a` comes before `b`" rules over arbitrary values.
+///
+/// The rules do not need to be acyclic (or even consistent) as a whole, only on the subsets that are
+/// actually sorted.
+///
+/// ```
+/// use aoc::graph::Precedence;
+///
+/// let rules: Precedence<u32> = [(47, 53), (97, 13), (97, 61), (75, 29), (61, 13), (29, 13)]
+///     .into_iter()
+///     .collect();
+///
+/// assert!(rules.is_ordered(&[75, 47, 61, 53, 29]));
+/// assert!(!rules.is_ordered(&[61, 13, 29]));
+/// assert_eq!(rules.sort(&[61, 13, 29]), Ok(vec![29, 61, 13]));
+///
+/// let cyclic: Precedence<char> = [('a', 'b'), ('b', 'a')].into_iter().collect();
+/// assert!(cyclic.sort(&['a', 'b']).is_err());
+/// assert_eq!(cyclic.sort(&['a', 'c']), Ok(vec!['a', 'c']));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Precedence<T> {
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T: Hash + Eq + Ord + Clone> Precedence<T> {
+    pub fn new() -> Self {
+        Self {
+            successors: HashMap::new(),
+        }
+    }
+
+    /// `add` records that `before` must come before `after`.
+    pub fn add(&mut self, before: T, after: T) {
+        self.successors.entry(before).or_default().insert(after);
+    }
+
+    /// `before` returns true if there is a rule that `a` comes before `b`.
+    pub fn before(&self, a: &T, b: &T) -> bool {
+        self.successors.get(a).is_some_and(|s| s.contains(b))
+    }
+
+    /// `is_ordered` returns true if no rule is violated by the order of `items`.
+    pub fn is_ordered(&self, items: &[T]) -> bool {
+        items
+            .iter()
+            .enumerate()
+            .all(|(i, a)| items[..i].iter().all(|b| !self.before(a, b)))
+    }
+
+    /// `sort` orders `items` so that every applicable rule is satisfied.
+    ///
+    /// Only rules between two of the `items` are considered, and the items are expected to be
+    /// distinct. Items that are not constrained relative to each other are ordered by their natural
+    /// ordering, so the result is deterministic.
+    ///
+    /// Returns an error naming a cycle if the rules are contradictory on `items`.
+    pub fn sort(&self, items: &[T]) -> Result<Vec<T>, CycleError<T>> {
+        let mut sorted = items.to_vec();
+        sorted.sort();
+
+        let index: HashMap<&T, usize> = sorted.iter().enumerate().map(|(i, t)| (t, i)).collect();
+        let adj: Vec<Vec<usize>> = sorted
+            .iter()
+            .map(|item| {
+                let mut next: Vec<usize> = self
+                    .successors
+                    .get(item)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|after| index.get(after).copied())
+                    .collect();
+                next.sort_unstable();
+                next
+            })
+            .collect();
+
+        match toposort(&adj) {
+            Ok(order) => Ok(order.into_iter().map(|i| sorted[i].clone()).collect()),
+            Err(CycleError { cycle }) => Err(CycleError {
+                cycle: cycle.into_iter().map(|i| sorted[i].clone()).collect(),
+            }),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Ord + Clone> Default for Precedence<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq + Ord + Clone> FromIterator<(T, T)> for Precedence<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut precedence = Self::new();
+        for (before, after) in iter {
+            precedence.add(before, after);
+        }
+        precedence
+    }
+}
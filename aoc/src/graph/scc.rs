@@ -0,0 +1,84 @@
+//! Strongly connected components.
+
+/// Tarjan's algorithm. Returns the strongly connected components of the graph.
+///
+/// Every node appears in exactly one component, and the nodes within a component are sorted. The
+/// components come out in reverse topological order: if there is an edge from component `a` to
+/// component `b` then `b` is listed before `a`.
+///
+/// The search is iterative, so deep graphs do not overflow the stack.
+///
+/// ```
+/// use aoc::graph::strongly_connected_components;
+///
+/// // 0 <-> 1 -> 2 <-> 3, 4 -> 4
+/// let adj = vec![vec![1], vec![0, 2], vec![3], vec![2], vec![4]];
+///
+/// assert_eq!(
+///     strongly_connected_components(&adj),
+///     vec![vec![2, 3], vec![0, 1], vec![4]]
+/// );
+/// ```
+pub fn strongly_connected_components(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let n = adj.len();
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut next_index = 0;
+
+    let mut stack = vec![];
+    let mut components = vec![];
+
+    // The call stack of the search: (node, index of the next edge to follow)
+    let mut calls: Vec<(usize, usize)> = vec![];
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        calls.push((root, 0));
+
+        while let Some((node, edge)) = calls.pop() {
+            if edge == 0 {
+                index[node] = next_index;
+                low[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&next) = adj[node].get(edge) {
+                calls.push((node, edge + 1));
+                if index[next] == UNVISITED {
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+                continue;
+            }
+
+            // All edges are done, `node` is the root of a component if nothing reached higher
+            if low[node] == index[node] {
+                let mut component = vec![];
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+        }
+    }
+
+    components
+}
@@ -0,0 +1,138 @@
+//! Names as small integers.
+//!
+//! Puzzles name their nodes `AAA`, `jqt` or `root`. Interning every name once while parsing lets the
+//! solution index `Vec`s by [`Symbol`] instead of hashing strings in the hot loop, and
+//! [`Interner::resolve`] turns a symbol back into its name for printing.
+use std::collections::HashMap;
+
+/// `Symbol` is an interned name, numbered from 0 in the order the names were first seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The dense index of the symbol, for indexing `Vec`s.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<Symbol> for usize {
+    fn from(symbol: Symbol) -> usize {
+        symbol.index()
+    }
+}
+
+/// `Interner` assigns a [`Symbol`] to every distinct name.
+///
+/// Names are borrowed from the input, nothing is copied.
+///
+/// ```
+/// use aoc::intern::Interner;
+///
+/// let mut names = Interner::new();
+/// let root = names.intern("root");
+/// let humn = names.intern("humn");
+/// assert_eq!(names.intern("root"), root);
+///
+/// assert_eq!(names.len(), 2);
+/// assert_eq!(names.resolve(humn), "humn");
+/// assert_eq!(names.get("humn"), Some(humn));
+/// assert_eq!(names.get("pppw"), None);
+/// assert_eq!(humn.index(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    names: Vec<&'a str>,
+    symbols: HashMap<&'a str, Symbol>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `intern` returns the symbol of `name`, assigning the next one if the name is new.
+    pub fn intern(&mut self, name: &'a str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(name);
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+
+    /// `get` returns the symbol of `name`, if it has been interned.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// `resolve` returns the name of `symbol`.
+    pub fn resolve(&self, symbol: Symbol) -> &'a str {
+        self.names[symbol.index()]
+    }
+
+    /// `names` returns all names, in symbol order.
+    pub fn names(&self) -> &[&'a str] {
+        &self.names
+    }
+
+    /// `symbols` iterates over all symbols, in order.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> {
+        (0..self.names.len() as u32).map(Symbol)
+    }
+
+    /// `len` returns the number of distinct names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// `is_empty` returns true if no name has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// `parse_adjacency` reads one node per line in the form `name <separator> neighbours`, like
+/// `AAA = (BBB, CCC)` or `jqt: rhn xhk nvd`, into a directed adjacency list over dense indices and
+/// the table of names.
+///
+/// Neighbours are split on commas and whitespace, brackets around the list are ignored. Their order
+/// is kept, so `adj[i][0]` is the first one listed. Nodes only mentioned as neighbours get an index
+/// with no edges of their own.
+///
+/// ```
+/// use aoc::intern::parse_adjacency;
+///
+/// let (adj, names) = parse_adjacency("AAA = (BBB, CCC)\nBBB = (DDD, EEE)", " = ");
+/// assert_eq!(names.names(), ["AAA", "BBB", "CCC", "DDD", "EEE"]);
+/// assert_eq!(adj, [vec![1, 2], vec![3, 4], vec![], vec![], vec![]]);
+///
+/// let (adj, names) = parse_adjacency("you: bbb ccc\nbbb: out", ": ");
+/// let index = |name| names.get(name).unwrap().index();
+/// assert_eq!(adj[index("bbb")], [index("out")]);
+/// ```
+pub fn parse_adjacency<'a>(input: &'a str, separator: &str) -> (Vec<Vec<usize>>, Interner<'a>) {
+    let mut names = Interner::new();
+    let mut adj: Vec<Vec<usize>> = vec![];
+
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (name, neighbours) = line
+            .split_once(separator)
+            .unwrap_or_else(|| panic!("expected {separator:?} in {line:?}"));
+        let node = names.intern(name.trim()).index();
+        let neighbours = neighbours
+            .trim()
+            .trim_start_matches(['(', '[', '{'])
+            .trim_end_matches([')', ']', '}'])
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|neighbour| !neighbour.is_empty())
+            .map(|neighbour| names.intern(neighbour).index())
+            .collect::<Vec<_>>();
+
+        adj.resize(names.len(), vec![]);
+        adj[node].extend(neighbours);
+    }
+
+    (adj, names)
+}
@@ -0,0 +1,380 @@
+//! Sets of integers stored as ranges, and maps that shift ranges around.
+//!
+//! Puzzles love huge ranges that are far too big to iterate over. [`IntervalSet`] keeps them as a
+//! sorted list of disjoint half-open ranges, and [`RangeMap`] pushes whole ranges through piecewise
+//! offset mappings, splitting them where needed.
+use std::ops::{Range, RangeInclusive};
+
+use num_traits::PrimInt;
+
+/// `IntervalSet` is a set of integers stored as sorted, disjoint, non-adjacent half-open ranges.
+///
+/// Every operation keeps it normalised, so two sets with the same elements compare equal.
+///
+/// ```
+/// use aoc::interval::IntervalSet;
+///
+/// let mut set = IntervalSet::new();
+/// set.insert(10..15);
+/// set.insert_inclusive(3..=5);
+/// set.insert(12..21);
+/// set.insert(6..10);
+/// assert_eq!(set.ranges(), [3..21]);
+///
+/// set.remove(7..9);
+/// assert_eq!(set.ranges(), [3..7, 9..21]);
+/// assert!(set.contains(3) && !set.contains(8));
+/// assert_eq!(set.total_len(), 16);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// The sorted, disjoint ranges making up the set.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |sum, r| sum + (r.end - r.start))
+    }
+
+    /// The smallest integer in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The largest integer in the set.
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    /// Adds every integer in `range` to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Every range touching or overlapping the new one is merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first == last {
+            range
+        } else {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Adds every integer in `range` to the set.
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start <= end {
+            self.insert(start..end + T::one());
+        }
+    }
+
+    /// Removes every integer in `range` from the set.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        // Only the first and last overlapping ranges can stick out on either side
+        let left = self.ranges[first].start..range.start;
+        let right = range.end..self.ranges[last - 1].end;
+        let kept = [left, right].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// `true` if every integer in `range` is in the set.
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// `true` if any integer in `range` is in the set.
+    pub fn overlaps(&self, range: Range<T>) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges.get(i).is_some_and(|r| r.start < range.end)
+    }
+
+    /// The part of the set inside `range`.
+    pub fn slice(&self, range: Range<T>) -> Self {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| r.start.max(range.start)..r.end.min(range.end))
+            .filter(|r| !r.is_empty())
+            .collect();
+        Self { ranges }
+    }
+
+    /// Every integer in either set.
+    ///
+    /// ```
+    /// use aoc::interval::IntervalSet;
+    ///
+    /// let a = IntervalSet::from_iter([0..5, 10..15]);
+    /// let b = IntervalSet::from_iter([3..12, 20..30]);
+    /// assert_eq!(a.union(&b).ranges(), [0..15, 20..30]);
+    /// assert_eq!(a.intersection(&b).ranges(), [3..5, 10..12]);
+    /// assert_eq!(a.difference(&b).ranges(), [0..3, 12..15]);
+    /// assert_eq!(b.difference(&a).ranges(), [5..10, 20..30]);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in &other.ranges {
+            set.insert(range.clone());
+        }
+        set
+    }
+
+    /// Every integer in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Every integer in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in &other.ranges {
+            set.remove(range.clone());
+        }
+        set
+    }
+
+    /// `true` if every integer in `self` is also in `other`.
+    ///
+    /// ```
+    /// use aoc::interval::IntervalSet;
+    ///
+    /// let a = IntervalSet::from_iter([2..9]);
+    /// let b = IntervalSet::from_iter([3..8]);
+    /// assert!(b.is_subset(&a) && !a.is_subset(&b));
+    /// assert!(!a.is_disjoint(&b));
+    /// assert!(a.is_disjoint(&IntervalSet::from_iter([9..10])));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.ranges.iter().all(|r| other.contains_range(r.clone()))
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.ranges.iter().all(|r| !other.overlaps(r.clone()))
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = Self::new();
+        set.insert_inclusive(range);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+/// `RangeMap` is a piecewise mapping: every integer in a source range is shifted to the same
+/// position in its destination range. Integers outside every source range map to themselves.
+///
+/// ```
+/// use aoc::interval::{IntervalSet, RangeMap};
+///
+/// // 98..100 -> 50..52, 50..98 -> 52..100
+/// let map = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+/// assert_eq!(map.map(79u64), 81);
+/// assert_eq!(map.map(99), 51);
+/// assert_eq!(map.map(10), 10);
+///
+/// assert_eq!(map.map_range(45..60), [45..50, 52..62]);
+/// assert_eq!(map.map_range(97..102), [99..100, 50..52, 100..102]);
+///
+/// let seeds = IntervalSet::from_iter([45..60, 97..102]);
+/// assert_eq!(map.map_set(&seeds).ranges(), [45..62, 99..102]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// `(source, destination start)`, sorted by source
+    segments: Vec<(Range<T>, T)>,
+}
+
+impl<T: PrimInt> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> RangeMap<T> {
+    pub fn new() -> Self {
+        Self { segments: vec![] }
+    }
+
+    /// Maps `source` to the range of the same length starting at `destination`. Source ranges must
+    /// not overlap.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+        let i = self
+            .segments
+            .partition_point(|(s, _)| s.start < source.start);
+        debug_assert!(
+            self.segments
+                .get(i)
+                .is_none_or(|(s, _)| source.end <= s.start)
+                && (i == 0 || self.segments[i - 1].0.end <= source.start),
+            "source ranges overlap"
+        );
+        self.segments.insert(i, (source, destination));
+    }
+
+    /// The `(source, destination start)` segments, sorted by source.
+    pub fn segments(&self) -> &[(Range<T>, T)] {
+        &self.segments
+    }
+
+    pub fn map(&self, value: T) -> T {
+        let i = self.segments.partition_point(|(s, _)| s.end <= value);
+        match self.segments.get(i) {
+            Some((source, destination)) if source.start <= value => {
+                value - source.start + *destination
+            }
+            _ => value,
+        }
+    }
+
+    /// Maps every integer in `range`. The result is one range per piece of `range` that is shifted
+    /// by a different amount, in the order of `range`.
+    pub fn map_range(&self, range: Range<T>) -> Vec<Range<T>> {
+        let mut output = vec![];
+        let mut start = range.start;
+        let mut i = self.segments.partition_point(|(s, _)| s.end <= start);
+
+        while start < range.end {
+            let piece = match self.segments.get(i) {
+                // Inside a segment: shift up to the end of it
+                Some((source, destination)) if source.start <= start => {
+                    let end = source.end.min(range.end);
+                    i += 1;
+                    (start - source.start + *destination)..(end - source.start + *destination)
+                }
+                // Before a segment: unchanged up to the start of it
+                Some((source, _)) => start..source.start.min(range.end),
+                None => start..range.end,
+            };
+            start = start + (piece.end - piece.start);
+            output.push(piece);
+        }
+        output
+    }
+
+    /// Maps every integer in `set`.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter()
+            .flat_map(|range| self.map_range(range.clone()))
+            .collect()
+    }
+}
+
+impl<T: PrimInt> Extend<(Range<T>, T)> for RangeMap<T> {
+    fn extend<I: IntoIterator<Item = (Range<T>, T)>>(&mut self, iter: I) {
+        for (source, destination) in iter {
+            self.insert(source, destination);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<(Range<T>, T)> for RangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
@@ -252,4 +252,47 @@ pub trait IterJunk: Iterator {
     {
         self.reduce(|a, b| if f(&a) <= f(&b) { a } else { b })
     }
+
+    /// `big_sum` adds up the elements without overflowing. It works in `i128` and only switches to
+    /// a `BigInt` once that is not enough. Needs the `bigint` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc::iterstuff::IterJunk;
+    /// use aoc::bigint::BigInt;
+    ///
+    /// assert_eq!([1u64, 2, 3].into_iter().big_sum(), BigInt::from(6));
+    ///
+    /// let huge = std::iter::repeat(u128::MAX).take(3).big_sum();
+    /// assert_eq!(huge, BigInt::from(u128::MAX) * 3);
+    /// ```
+    #[cfg(feature = "bigint")]
+    fn big_sum(self) -> num_bigint::BigInt
+    where
+        Self: Sized,
+        Self::Item: num_traits::ToPrimitive + Into<num_bigint::BigInt>,
+    {
+        crate::bigint::promoting_fold(self, 0, i128::checked_add, |a, b| a + b)
+    }
+
+    /// `big_product` multiplies the elements without overflowing, like [`big_sum`](IterJunk::big_sum).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc::iterstuff::IterJunk;
+    ///
+    /// // 30!
+    /// let factorial = (1..=30u32).big_product();
+    /// assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+    /// ```
+    #[cfg(feature = "bigint")]
+    fn big_product(self) -> num_bigint::BigInt
+    where
+        Self: Sized,
+        Self::Item: num_traits::ToPrimitive + Into<num_bigint::BigInt>,
+    {
+        crate::bigint::promoting_fold(self, 1, i128::checked_mul, |a, b| a * b)
+    }
 }
@@ -1,19 +1,33 @@
 pub mod algs;
+#[cfg(feature = "bigint")]
+pub mod bigint;
+pub mod bitset;
+pub mod cycle;
+pub mod diagram;
+pub mod digits;
+pub mod error;
+pub mod geom;
 pub mod get_mut;
+pub mod graph;
+pub mod intern;
+pub mod interval;
 pub mod iterstuff;
+pub mod linalg;
+pub mod math;
+pub mod parse;
 mod product;
 pub mod stringstuff;
 mod time;
 pub mod tree;
 pub mod unionfind;
 
-pub use aoc_macro::input_str;
+pub use aoc_macro::{input_str, scan};
 pub use get_mut::GetMany;
 pub use iterstuff::IterJunk;
 pub use product::cartesian_product;
 pub use time::time;
-pub use tree::{Node, Tree};
-pub use unionfind::UnionFind;
+pub use tree::rc::{Node, Tree};
+pub use unionfind::{KeyedUnionFind, RollbackUnionFind, UnionFind};
 
 /// Read the entire file into memory as a string.
 /// Then split it on newlines.
@@ -0,0 +1,376 @@
+//! Exact linear algebra over [`Rational`] numbers.
+//!
+//! Puzzles that boil down to a system of linear equations usually want an exact (and often
+//! integer) answer, so everything here works with fractions instead of floats.
+use std::{
+    fmt,
+    ops::{Index, IndexMut, Mul},
+};
+
+use num_traits::{One, PrimInt, Signed, Zero};
+
+pub use crate::math::Rational;
+
+/// `Matrix` is a dense `rows x cols` matrix of [`Rational`] numbers, stored row by row.
+///
+/// ```
+/// use aoc::linalg::{Matrix, Rational};
+///
+/// let m = Matrix::<i64>::from_rows([[1, 2], [3, 4]]);
+/// assert_eq!(m[(1, 0)], Rational::from(3));
+/// assert_eq!(m.determinant(), Rational::from(-2));
+/// assert_eq!(&m * &m.inverse().unwrap(), Matrix::identity(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational<T>>,
+}
+
+/// `Solution` is the set of solutions of a linear system `A x = b`, see [`Matrix::solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution<T> {
+    /// The equations contradict each other.
+    None,
+    /// Exactly one `x` satisfies every equation.
+    Unique(Vec<Rational<T>>),
+    /// Infinitely many solutions: `particular + t_1 * free[0] + t_2 * free[1] + ...` for any `t_i`.
+    Parametric {
+        particular: Vec<Rational<T>>,
+        free: Vec<Vec<Rational<T>>>,
+    },
+}
+
+impl<T: PrimInt + Signed> Solution<T> {
+    /// `unique` returns the solution if there is exactly one.
+    pub fn unique(self) -> Option<Vec<Rational<T>>> {
+        match self {
+            Self::Unique(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// `integer` returns the solution if there is exactly one and every component is a whole
+    /// number.
+    ///
+    /// ```
+    /// use aoc::linalg::Matrix;
+    ///
+    /// // x + y = 3, x - y = 1
+    /// let m = Matrix::<i64>::from_rows([[1, 1], [1, -1]]);
+    /// assert_eq!(m.solve([3, 1]).integer(), Some(vec![2, 1]));
+    /// assert_eq!(m.solve([3, 2]).integer(), None);
+    /// ```
+    pub fn integer(self) -> Option<Vec<T>> {
+        self.unique()?.iter().map(Rational::to_integer).collect()
+    }
+}
+
+impl<T: PrimInt + Signed> Matrix<T> {
+    /// Creates a `rows x cols` matrix filled with zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![Rational::zero(); rows * cols],
+        }
+    }
+
+    /// Creates the `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = Rational::one();
+        }
+        m
+    }
+
+    /// Creates a matrix from its rows. Panics if the rows have different lengths.
+    pub fn from_rows<R, V>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = V>,
+        V: Into<Rational<T>>,
+    {
+        let mut data = vec![];
+        let mut n_rows = 0;
+        for row in rows {
+            data.extend(row.into_iter().map(Into::into));
+            n_rows += 1;
+        }
+
+        let cols = data.len().checked_div(n_rows).unwrap_or(0);
+        assert_eq!(cols * n_rows, data.len(), "rows have different lengths");
+        Self {
+            rows: n_rows,
+            cols,
+            data,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, r: usize) -> &[Rational<T>] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::zeros(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t[(c, r)] = self[(r, c)];
+            }
+        }
+        t
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for c in 0..self.cols {
+            self.data.swap(a * self.cols + c, b * self.cols + c);
+        }
+    }
+
+    /// Brings the matrix into reduced row echelon form with Gauss-Jordan elimination.
+    ///
+    /// Returns the pivot column of every non-zero row, in order. The number of pivots is the rank.
+    ///
+    /// ```
+    /// use aoc::linalg::Matrix;
+    ///
+    /// let mut m = Matrix::<i64>::from_rows([[2, 4, 2], [1, 2, 3]]);
+    /// assert_eq!(m.rref(), vec![0, 2]);
+    /// assert_eq!(m, Matrix::from_rows([[1, 2, 0], [0, 0, 1]]));
+    /// ```
+    pub fn rref(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+        for c in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..self.rows).find(|&i| !self[(i, c)].is_zero()) else {
+                continue;
+            };
+            self.swap_rows(r, pivot);
+
+            let scale = self[(r, c)].recip();
+            for j in c..self.cols {
+                self[(r, j)] *= scale;
+            }
+
+            for i in 0..self.rows {
+                let factor = self[(i, c)];
+                if i == r || factor.is_zero() {
+                    continue;
+                }
+                for j in c..self.cols {
+                    let delta = factor * self[(r, j)];
+                    self[(i, j)] -= delta;
+                }
+            }
+
+            pivots.push(c);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+        pivots
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().rref().len()
+    }
+
+    /// Returns the determinant. Panics if the matrix is not square.
+    ///
+    /// ```
+    /// use aoc::linalg::{Matrix, Rational};
+    ///
+    /// let m = Matrix::<i64>::from_rows([[0, 2, 1], [1, 0, 0], [3, 1, 5]]);
+    /// assert_eq!(m.determinant(), Rational::from(-9));
+    /// assert_eq!(Matrix::<i64>::from_rows([[1, 2], [2, 4]]).determinant(), Rational::from(0));
+    /// ```
+    pub fn determinant(&self) -> Rational<T> {
+        assert!(self.is_square(), "determinant of a non-square matrix");
+        let mut m = self.clone();
+        let mut det = Rational::one();
+
+        for c in 0..m.cols {
+            let Some(pivot) = (c..m.rows).find(|&i| !m[(i, c)].is_zero()) else {
+                return Rational::zero();
+            };
+            if pivot != c {
+                m.swap_rows(c, pivot);
+                det = -det;
+            }
+
+            let p = m[(c, c)];
+            det *= p;
+            for i in c + 1..m.rows {
+                let factor = m[(i, c)] / p;
+                if factor.is_zero() {
+                    continue;
+                }
+                for j in c..m.cols {
+                    let delta = factor * m[(c, j)];
+                    m[(i, j)] -= delta;
+                }
+            }
+        }
+        det
+    }
+
+    /// Returns the inverse, or `None` if the matrix is singular. Panics if the matrix is not square.
+    ///
+    /// ```
+    /// use aoc::linalg::Matrix;
+    ///
+    /// let m = Matrix::<i64>::from_rows([[2, 1], [1, 1]]);
+    /// assert_eq!(m.inverse(), Some(Matrix::from_rows([[1, -1], [-1, 2]])));
+    /// assert_eq!(Matrix::<i64>::from_rows([[1, 2], [2, 4]]).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        assert!(self.is_square(), "inverse of a non-square matrix");
+        let n = self.rows;
+
+        // Reduce [A | I] to [I | A^-1]
+        let mut augmented = Self::zeros(n, 2 * n);
+        for r in 0..n {
+            for c in 0..n {
+                augmented[(r, c)] = self[(r, c)];
+            }
+            augmented[(r, n + r)] = Rational::one();
+        }
+
+        let pivots = augmented.rref();
+        if pivots.len() < n || pivots[n - 1] >= n {
+            return None;
+        }
+
+        let mut inverse = Self::zeros(n, n);
+        for r in 0..n {
+            for c in 0..n {
+                inverse[(r, c)] = augmented[(r, n + c)];
+            }
+        }
+        Some(inverse)
+    }
+
+    /// Solves `self * x = rhs` exactly with Gaussian elimination.
+    ///
+    /// Panics if `rhs` does not have one entry per row.
+    ///
+    /// ```
+    /// use aoc::linalg::{Matrix, Rational, Solution};
+    ///
+    /// // 2x + y = 1, x + 3y = 2
+    /// let m = Matrix::<i64>::from_rows([[2, 1], [1, 3]]);
+    /// assert_eq!(
+    ///     m.solve([1, 2]),
+    ///     Solution::Unique(vec![Rational::new(1, 5), Rational::new(3, 5)])
+    /// );
+    ///
+    /// // x + y = 1 and x + y = 2 contradict each other
+    /// let m = Matrix::<i64>::from_rows([[1, 1], [1, 1]]);
+    /// assert_eq!(m.solve([1, 2]), Solution::None);
+    ///
+    /// // x + y + z = 6 and y - z = 1 leave one degree of freedom
+    /// let m = Matrix::<i64>::from_rows([[1, 1, 1], [0, 1, -1]]);
+    /// let Solution::Parametric { particular, free } = m.solve([6, 1]) else {
+    ///     panic!("expected infinitely many solutions");
+    /// };
+    /// assert_eq!(particular, [5, 1, 0].map(Rational::from));
+    /// assert_eq!(free, vec![[-2, 1, 1].map(Rational::from)]);
+    /// ```
+    pub fn solve<V: Into<Rational<T>>>(&self, rhs: impl IntoIterator<Item = V>) -> Solution<T> {
+        let rhs: Vec<Rational<T>> = rhs.into_iter().map(Into::into).collect();
+        assert_eq!(rhs.len(), self.rows, "right hand side has the wrong length");
+
+        let mut augmented = Self::zeros(self.rows, self.cols + 1);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                augmented[(r, c)] = self[(r, c)];
+            }
+            augmented[(r, self.cols)] = rhs[r];
+        }
+
+        let pivots = augmented.rref();
+        if pivots.last() == Some(&self.cols) {
+            return Solution::None;
+        }
+
+        // Every free variable is set to 0 for the particular solution
+        let mut particular = vec![Rational::zero(); self.cols];
+        for (r, &c) in pivots.iter().enumerate() {
+            particular[c] = augmented[(r, self.cols)];
+        }
+
+        if pivots.len() == self.cols {
+            return Solution::Unique(particular);
+        }
+
+        let free = (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|f| {
+                let mut v = vec![Rational::zero(); self.cols];
+                v[f] = Rational::one();
+                for (r, &c) in pivots.iter().enumerate() {
+                    v[c] = -augmented[(r, f)];
+                }
+                v
+            })
+            .collect();
+
+        Solution::Parametric { particular, free }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = Rational<T>;
+
+    fn index(&self, (r, c): (usize, usize)) -> &Self::Output {
+        debug_assert!(r < self.rows && c < self.cols);
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Self::Output {
+        debug_assert!(r < self.rows && c < self.cols);
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+impl<T: PrimInt + Signed> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Matrix<T> {
+        assert_eq!(self.cols, rhs.rows, "matrix dimensions do not match");
+        let mut product = Matrix::zeros(self.rows, rhs.cols);
+        for r in 0..self.rows {
+            for c in 0..rhs.cols {
+                product[(r, c)] = (0..self.cols)
+                    .map(|k| self[(r, k)] * rhs[(k, c)])
+                    .fold(Rational::zero(), |a, b| a + b);
+            }
+        }
+        product
+    }
+}
+
+impl<T: PrimInt + Signed + fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.rows {
+            let row: Vec<String> = self.row(r).iter().map(ToString::to_string).collect();
+            writeln!(f, "[{}]", row.join(", "))?;
+        }
+        Ok(())
+    }
+}
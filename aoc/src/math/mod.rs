@@ -0,0 +1,9 @@
+//! Exact integer math that keeps coming up in puzzles.
+mod number_theory;
+pub mod poly;
+mod rational;
+
+pub use number_theory::{
+    crt, extended_gcd, gcd, gcd_all, lcm, lcm_all, mod_inverse, mod_mul, mod_pow, modulo, CrtError,
+};
+pub use rational::Rational;
@@ -0,0 +1,287 @@
+//! gcd, lcm, modular arithmetic and the Chinese Remainder Theorem.
+//!
+//! Everything is generic over [`num_traits::PrimInt`], so the same functions work for `u32`, `i64`,
+//! `i128`, etc. Functions that need negative intermediate values also require
+//! [`num_traits::Signed`].
+use std::fmt;
+
+use num_traits::{PrimInt, Signed};
+
+fn abs<T: PrimInt>(x: T) -> T {
+    if x < T::zero() {
+        T::zero() - x
+    } else {
+        x
+    }
+}
+
+/// `gcd` returns the greatest common divisor of `a` and `b`. The result is never negative, and
+/// `gcd(0, 0) = 0`.
+///
+/// # Panics
+///
+/// On overflow if the result does not fit in `T`, which only happens for `gcd(T::MIN, T::MIN)` and
+/// `gcd(T::MIN, 0)` of a signed type. Other calls with `T::MIN` are fine.
+///
+/// ```
+/// use aoc::math::gcd;
+///
+/// assert_eq!(gcd(12u32, 18), 6);
+/// assert_eq!(gcd(-12i64, 18), 6);
+/// assert_eq!(gcd(0i128, 7), 7);
+/// assert_eq!(gcd(i64::MIN, 6), 2);
+/// assert_eq!(gcd(i8::MIN, -1), 1);
+/// ```
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    if T::min_value() == T::zero() {
+        let (mut a, mut b) = (a, b);
+        while b != T::zero() {
+            (a, b) = (b, a % b);
+        }
+        return a;
+    }
+
+    // Work on non-positive values, since every positive value can be negated but `T::MIN` cannot
+    let non_positive = |x: T| if x > T::zero() { T::zero() - x } else { x };
+    let (mut a, mut b) = (non_positive(a), non_positive(b));
+    while b != T::zero() {
+        // `T::MIN % -1` overflows, and everything is a multiple of 1
+        if b == T::zero() - T::one() {
+            return T::one();
+        }
+        (a, b) = (b, a % b);
+    }
+    T::zero() - a
+}
+
+/// `lcm` returns the least common multiple of `a` and `b`. The result is never negative, and it is 0
+/// if either argument is 0.
+///
+/// # Panics
+///
+/// On overflow if the result does not fit in `T`.
+///
+/// ```
+/// use aoc::math::lcm;
+///
+/// assert_eq!(lcm(4u64, 6), 12);
+/// assert_eq!(lcm(-4i32, 6), 12);
+/// assert_eq!(lcm(0u8, 6), 0);
+/// ```
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    if a == T::zero() || b == T::zero() {
+        return T::zero();
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+/// `gcd_all` returns the greatest common divisor of every value, or 0 if there are none.
+///
+/// ```
+/// use aoc::math::gcd_all;
+///
+/// assert_eq!(gcd_all([12u32, 18, 27]), 3);
+/// assert_eq!(gcd_all(Vec::<i64>::new()), 0);
+/// ```
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// `lcm_all` returns the least common multiple of every value, or 1 if there are none.
+///
+/// ```
+/// use aoc::math::lcm_all;
+///
+/// assert_eq!(lcm_all([2u64, 3, 4, 5, 6]), 60);
+/// assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+/// ```
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// `extended_gcd` returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+///
+/// ```
+/// use aoc::math::extended_gcd;
+///
+/// let (g, x, y) = extended_gcd(240i64, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `modulo` returns `a mod m` in the range `0..m`, even when `a` is negative.
+///
+/// ```
+/// use aoc::math::modulo;
+///
+/// assert_eq!(modulo(7i32, 5), 2);
+/// assert_eq!(modulo(-7i32, 5), 3);
+/// assert_eq!(modulo(-10i32, 5), 0);
+/// ```
+pub fn modulo<T: PrimInt>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::zero() {
+        r + abs(m)
+    } else {
+        r
+    }
+}
+
+/// `mod_mul` returns `a * b mod m` without overflowing, as long as `m` itself fits.
+///
+/// ```
+/// use aoc::math::mod_mul;
+///
+/// let m = 1_000_000_000_000_000_003u64;
+/// assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+/// ```
+pub fn mod_mul<T: PrimInt>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    if let Some(product) = a.checked_mul(&b) {
+        return product % m;
+    }
+
+    // Double and add, every intermediate value stays below 2m
+    let mut result = T::zero();
+    while b > T::zero() {
+        if b & T::one() == T::one() {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b >> 1;
+    }
+    result
+}
+
+/// `a + b mod m` for `a, b` in `0..m`.
+fn add_mod<T: PrimInt>(a: T, b: T, m: T) -> T {
+    // a + b might overflow, m - b never does
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `mod_pow` returns `base^exp mod m` by repeated squaring.
+///
+/// ```
+/// use aoc::math::mod_pow;
+///
+/// assert_eq!(mod_pow(4u32, 13, 497), 445);
+/// assert_eq!(mod_pow(2i128, 1_000_000, 1_000_000_007), 235042059);
+/// assert_eq!(mod_pow(5u64, 0, 1), 0);
+/// ```
+pub fn mod_pow<T: PrimInt>(base: T, mut exp: T, m: T) -> T {
+    let mut base = modulo(base, m);
+    let mut result = T::one() % m;
+    while exp > T::zero() {
+        if exp & T::one() == T::one() {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp = exp >> 1;
+    }
+    result
+}
+
+/// `mod_inverse` returns `x` in `0..m` such that `a * x = 1 mod m`, if it exists. It exists exactly
+/// when `a` and `m` are coprime.
+///
+/// ```
+/// use aoc::math::mod_inverse;
+///
+/// assert_eq!(mod_inverse(3i64, 11), Some(4));
+/// assert_eq!(mod_inverse(-3i64, 11), Some(7));
+/// assert_eq!(mod_inverse(4i64, 8), None);
+/// ```
+pub fn mod_inverse<T: PrimInt + Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    (g == T::one()).then(|| modulo(x, m))
+}
+
+/// `CrtError` is returned by [`crt`] when the congruences have no common solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrtError {
+    /// The index of the first congruence that contradicts the ones before it.
+    pub index: usize,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "congruence {} contradicts the congruences before it",
+            self.index
+        )
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// Chinese Remainder Theorem. Solves the system `x = r_i mod m_i` for the `(r_i, m_i)` pairs.
+///
+/// The moduli do not need to be coprime. Returns `(x, m)` where `m` is the lcm of the moduli and `x`
+/// is the unique solution in `0..m`, or an error naming the first congruence that is inconsistent.
+///
+/// ```
+/// use aoc::math::{crt, CrtError};
+///
+/// assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+///
+/// // Moduli 6 and 4 share a factor of 2, and 5 and 3 agree on it
+/// assert_eq!(crt([(5i64, 6), (3, 4)]), Ok((11, 12)));
+/// assert_eq!(crt([(5i64, 6), (2, 4)]), Err(CrtError { index: 1 }));
+///
+/// // Large moduli work with i128
+/// let (x, m) = crt([(1i128, 1_000_000_007), (2, 998_244_353)]).unwrap();
+/// assert_eq!((x % 1_000_000_007, x % 998_244_353), (1, 2));
+/// assert_eq!(m, 1_000_000_007 * 998_244_353);
+/// ```
+pub fn crt<T: PrimInt + Signed>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Result<(T, T), CrtError> {
+    let mut x = T::zero();
+    let mut m = T::one();
+
+    for (index, (r, n)) in congruences.into_iter().enumerate() {
+        let n = abs(n);
+        let r = modulo(r, n);
+
+        // Solve x + m * k = r (mod n) for k
+        let g = gcd(m, n);
+        let diff = r - modulo(x, n);
+        if diff % g != T::zero() {
+            return Err(CrtError { index });
+        }
+
+        let n_g = n / g;
+        // m / g and n / g are coprime, so the inverse exists
+        let inverse = mod_inverse(m / g, n_g).unwrap();
+        let k = mod_mul(modulo(diff / g, n_g), inverse, n_g);
+
+        let next_m = m * n_g;
+        x = add_mod(x, mod_mul(m, k, next_m), next_m);
+        m = next_m;
+    }
+
+    Ok((x, m))
+}
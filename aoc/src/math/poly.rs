@@ -0,0 +1,238 @@
+//! Sequences generated by polynomials.
+//!
+//! A sequence comes from a polynomial of degree `d` exactly when its `d`-th differences are
+//! constant. That is enough to extend it in both directions, to jump straight to a far away
+//! element, or to recover the polynomial itself.
+use std::fmt;
+
+use num_traits::{Num, One, PrimInt, Signed, Zero};
+
+use super::Rational;
+
+/// The differences between neighbouring elements, one shorter than `sequence`.
+///
+/// ```
+/// use aoc::math::poly::differences;
+///
+/// assert_eq!(differences(&[1, 3, 6, 10]), [2, 3, 4]);
+/// ```
+pub fn differences<T: Num + Copy>(sequence: &[T]) -> Vec<T> {
+    sequence.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// The difference table of `sequence`, from the sequence itself down to the first row that is all
+/// zeros (or a single element, if no row is).
+fn table<T: Num + Copy>(sequence: &[T]) -> Vec<Vec<T>> {
+    let mut rows = vec![sequence.to_vec()];
+    loop {
+        let last = rows.last().unwrap();
+        if last.len() <= 1 || last.iter().all(|n| n.is_zero()) {
+            return rows;
+        }
+        rows.push(differences(last));
+    }
+}
+
+/// The lowest degree of a polynomial that generates the whole sequence, if the sequence is long
+/// enough to tell. A constant sequence has degree 0.
+///
+/// `None` means no polynomial of a degree below `len - 1` fits. There always is one of degree
+/// `len - 1`, but it predicts nothing.
+///
+/// ```
+/// use aoc::math::poly::degree;
+///
+/// assert_eq!(degree(&[5, 5, 5]), Some(0));
+/// assert_eq!(degree(&[0, 3, 6, 9, 12, 15]), Some(1));
+/// assert_eq!(degree(&[1, 3, 6, 10, 15, 21]), Some(2));
+/// assert_eq!(degree(&[1, 2, 4, 8, 16, 32]), None);
+/// ```
+pub fn degree<T: Num + Copy>(sequence: &[T]) -> Option<usize> {
+    let rows = table(sequence);
+    let last = rows.last().unwrap();
+    if !last.iter().all(|n| n.is_zero()) || last.is_empty() {
+        return None;
+    }
+    // The row of zeros is at depth d + 1, unless the sequence itself is all zeros
+    Some(rows.len().saturating_sub(2))
+}
+
+/// The element after the end of the sequence, assuming its differences eventually become constant.
+///
+/// ```
+/// use aoc::math::poly::extrapolate_forward;
+///
+/// assert_eq!(extrapolate_forward(&[10, 13, 16, 21, 30, 45]), 68);
+/// ```
+pub fn extrapolate_forward<T: Num + Copy>(sequence: &[T]) -> T {
+    table(sequence)
+        .iter()
+        .filter_map(|row| row.last())
+        .fold(T::zero(), |sum, &n| sum + n)
+}
+
+/// The element before the start of the sequence, assuming its differences eventually become
+/// constant.
+///
+/// ```
+/// use aoc::math::poly::extrapolate_backward;
+///
+/// assert_eq!(extrapolate_backward(&[10, 13, 16, 21, 30, 45]), 5);
+/// ```
+pub fn extrapolate_backward<T: Num + Copy>(sequence: &[T]) -> T {
+    table(sequence)
+        .iter()
+        .filter_map(|row| row.first())
+        .rev()
+        .fold(T::zero(), |prev, &n| n - prev)
+}
+
+/// The element at index `x` (which may be huge or negative) of the sequence generated by the lowest
+/// degree polynomial through `sequence`, with `sequence[0]` at index 0.
+///
+/// Uses Newton's forward formula `f(x) = sum C(x, k) * (k-th difference at 0)`, which stays in
+/// integers all the way.
+///
+/// ```
+/// use aoc::math::poly::extrapolate;
+///
+/// // n^2 + n + 1
+/// let sequence = [1i128, 3, 7, 13];
+/// assert_eq!(extrapolate(&sequence, 4), 21);
+/// assert_eq!(extrapolate(&sequence, -1), 1);
+/// assert_eq!(extrapolate(&sequence, 1_000_000_000), 1_000_000_001_000_000_001);
+/// ```
+pub fn extrapolate<T: PrimInt + Signed>(sequence: &[T], x: T) -> T {
+    let mut sum = T::zero();
+    let mut binomial = T::one();
+    let mut k = T::zero();
+    for row in table(sequence) {
+        let Some(&first) = row.first() else {
+            break;
+        };
+        sum = sum + binomial * first;
+
+        // C(x, k + 1) = C(x, k) * (x - k) / (k + 1), always exact
+        binomial = binomial * (x - k) / (k + T::one());
+        k = k + T::one();
+    }
+    sum
+}
+
+/// `Polynomial` is a polynomial with exact [`Rational`] coefficients, lowest degree first.
+///
+/// ```
+/// use aoc::math::{poly::Polynomial, Rational};
+///
+/// // Fit a quadratic to three samples and evaluate it far away
+/// let p = Polynomial::interpolate(&[(65i128, 3_835), (196, 34_125), (327, 94_603)]);
+/// assert_eq!(p.degree(), Some(2));
+/// assert_eq!(p.eval(26_501_365).to_integer(), Some(617_729_401_414_635));
+///
+/// let half = Polynomial::interpolate(&[(0i64, 0), (1, 0), (2, 1)]);
+/// assert_eq!(half.eval(3), Rational::from(3));
+/// assert_eq!(format!("{half}"), "1/2x^2 - 1/2x");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polynomial<T> {
+    coefficients: Vec<Rational<T>>,
+}
+
+impl<T: PrimInt + Signed> Polynomial<T> {
+    /// Creates the polynomial `c[0] + c[1] x + c[2] x^2 + ...`.
+    pub fn new(coefficients: impl IntoIterator<Item = Rational<T>>) -> Self {
+        let mut coefficients: Vec<_> = coefficients.into_iter().collect();
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    /// The lowest degree polynomial through every `(x, y)` point (Lagrange interpolation). Panics
+    /// if two points share an `x`.
+    pub fn interpolate(points: &[(T, T)]) -> Self {
+        let mut coefficients = vec![Rational::zero(); points.len()];
+
+        for (i, &(xi, yi)) in points.iter().enumerate() {
+            // basis = prod (x - xj) / (xi - xj) over j != i, expanded into coefficients
+            let mut basis = vec![Rational::one()];
+            let mut denominator = T::one();
+            for (j, &(xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                assert!(xi != xj, "two points share an x");
+                basis.push(Rational::zero());
+                for k in (0..basis.len()).rev() {
+                    let shifted = if k > 0 {
+                        basis[k - 1]
+                    } else {
+                        Rational::zero()
+                    };
+                    basis[k] = shifted - basis[k] * Rational::from(xj);
+                }
+                denominator = denominator * (xi - xj);
+            }
+
+            let scale = Rational::new(yi, denominator);
+            for (c, b) in coefficients.iter_mut().zip(basis) {
+                *c += b * scale;
+            }
+        }
+
+        Self::new(coefficients)
+    }
+
+    /// The coefficients, lowest degree first, without trailing zeros.
+    pub fn coefficients(&self) -> &[Rational<T>] {
+        &self.coefficients
+    }
+
+    /// The degree, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Evaluates the polynomial at `x` with Horner's method.
+    pub fn eval(&self, x: impl Into<Rational<T>>) -> Rational<T> {
+        let x = x.into();
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |acc, &c| acc * x + c)
+    }
+}
+
+impl<T: PrimInt + Signed + fmt::Display> fmt::Display for Polynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+
+        let mut first = true;
+        for (power, c) in self.coefficients.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let sign = c.numer() < T::zero();
+            match (first, sign) {
+                (true, true) => write!(f, "-")?,
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+                (true, false) => {}
+            }
+            first = false;
+
+            let c = c.abs();
+            if !c.is_one() || power == 0 {
+                write!(f, "{c}")?;
+            }
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{power}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+//! Exact fractions.
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use num_traits::{One, PrimInt, Signed, Zero};
+
+use super::{gcd, lcm};
+
+/// `Rational` is an exact fraction `numer / denom`.
+///
+/// It is always kept in lowest terms with a positive denominator, so the derived equality and
+/// hashing compare values. Arithmetic panics on overflow in debug builds, just like the integer
+/// type it wraps; `Rational<i128>` is plenty for most puzzles.
+///
+/// ```
+/// use aoc::math::Rational;
+///
+/// let a = Rational::new(1i64, 3);
+/// let b = Rational::new(-4i64, 6);
+/// assert_eq!(b, Rational::new(2, -3));
+/// assert_eq!(a + b, Rational::new(-1, 3));
+/// assert_eq!(a * b, Rational::new(-2, 9));
+/// assert_eq!((a - b).to_integer(), Some(1));
+/// assert_eq!(format!("{}", a / b), "-1/2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: PrimInt + Signed> Rational<T> {
+    /// Creates the fraction `numer / denom`. Panics if `denom` is zero.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "denominator is zero");
+        let g = gcd(numer, denom);
+        let sign = denom.signum();
+        Self {
+            numer: numer / g * sign,
+            denom: denom / g * sign,
+        }
+    }
+
+    /// Creates the fraction `value / 1`.
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::one(),
+        }
+    }
+
+    /// The numerator, which carries the sign.
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    /// `to_integer` returns the value if it is a whole number.
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    /// Rounds towards negative infinity.
+    ///
+    /// ```
+    /// use aoc::math::Rational;
+    ///
+    /// assert_eq!(Rational::new(7i32, 2).floor(), 3);
+    /// assert_eq!(Rational::new(-7i32, 2).floor(), -4);
+    /// ```
+    pub fn floor(&self) -> T {
+        let q = self.numer / self.denom;
+        if self.numer % self.denom < T::zero() {
+            q - T::one()
+        } else {
+            q
+        }
+    }
+
+    /// Rounds towards positive infinity.
+    ///
+    /// ```
+    /// use aoc::math::Rational;
+    ///
+    /// assert_eq!(Rational::new(7i32, 2).ceil(), 4);
+    /// assert_eq!(Rational::new(-7i32, 2).ceil(), -3);
+    /// ```
+    pub fn ceil(&self) -> T {
+        let q = self.numer / self.denom;
+        if self.numer % self.denom > T::zero() {
+            q + T::one()
+        } else {
+            q
+        }
+    }
+
+    /// `recip` returns `1 / self`. Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl<T: PrimInt + Signed> Zero for Rational<T> {
+    fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+}
+
+impl<T: PrimInt + Signed> One for Rational<T> {
+    fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+}
+
+impl<T: PrimInt + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Scaling to the lcm keeps intermediate values as small as possible
+        let denom = lcm(self.denom, rhs.denom);
+        let numer = self.numer * (denom / self.denom) + rhs.numer * (denom / rhs.denom);
+        Self::new(numer, denom)
+    }
+}
+
+impl<T: PrimInt + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: PrimInt + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Cancel crosswise first so the products do not overflow needlessly
+        let g1 = gcd(self.numer, rhs.denom);
+        let g2 = gcd(rhs.numer, self.denom);
+        if g1.is_zero() || g2.is_zero() {
+            return Self::zero();
+        }
+        Self {
+            numer: (self.numer / g1) * (rhs.numer / g2),
+            denom: (self.denom / g2) * (rhs.denom / g1),
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+macro_rules! assign_ops {
+    ($($trait:ident $method:ident $op:tt),*) => {
+        $(
+            impl<T: PrimInt + Signed> $trait for Rational<T> {
+                fn $method(&mut self, rhs: Self) {
+                    *self = *self $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+assign_ops!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl<T: PrimInt + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PrimInt + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Both denominators are positive, so the sign of the difference decides
+        (*self - *other).numer.cmp(&T::zero())
+    }
+}
+
+impl<T: fmt::Display + One + PartialEq> fmt::Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! A small parser for puzzle inputs that borrows from the input instead of copying it.
+//!
+//! A [`Parser`] is a cursor into the input. Its methods consume tokens (literals, integers,
+//! identifiers) and the combinators take closures that parse one item, so a whole input format
+//! reads top to bottom:
+//!
+//! ```
+//! use aoc::parse::{self, Parser};
+//!
+//! let input = "Register A: 729\nRegister B: 0\n\nProgram: 0,1,5,4,3,0\n";
+//! let (registers, program) = parse::parse(input, |p: &mut Parser| {
+//!     let registers = p.lines(|p| {
+//!         p.literal("Register ")?;
+//!         let name = p.ident()?;
+//!         p.literal(": ")?;
+//!         Ok((name, p.uint::<u64>()?))
+//!     })?;
+//!     p.blank_line()?;
+//!     p.literal("Program: ")?;
+//!     Ok((registers, p.separated(",", |p| p.uint::<u8>())?))
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(registers, [("A", 729), ("B", 0)]);
+//! assert_eq!(program, [0, 1, 5, 4, 3, 0]);
+//! ```
+//!
+//! Errors point at the spot where parsing stopped:
+//!
+//! ```
+//! use aoc::parse::{self, Parser};
+//!
+//! let error = parse::parse("1, 2\n3, x", |p: &mut Parser| {
+//!     p.lines(|p| p.separated(", ", |p| p.int::<i32>()))
+//! })
+//! .unwrap_err();
+//! assert_eq!((error.line, error.column), (2, 4));
+//! assert_eq!(error.to_string(), "line 2, column 4: expected integer");
+//! ```
+//!
+//! For inputs that are a single regex per line, the [`inpt`](https://docs.rs/inpt) derive is still
+//! the shorter option.
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+pub use crate::error::ParseError as Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parses all of `input` with `f`. Trailing whitespace is allowed, anything else left over is an
+/// error.
+///
+/// Lists stop quietly at the first item that does not parse, so on failure the error reported is
+/// the one that got furthest into the input. That is usually the actual mistake, rather than the
+/// place where the list happened to end. An error that `f` built itself is kept, unless the parser
+/// recorded one further along.
+///
+/// ```
+/// use aoc::parse::{self, Error};
+///
+/// let error = parse::parse("abc", |_| Err::<(), _>(Error::at("abc", 2, "digit"))).unwrap_err();
+/// assert_eq!(error.to_string(), "line 1, column 3: expected digit");
+/// ```
+pub fn parse<'a, T>(input: &'a str, f: impl FnOnce(&mut Parser<'a>) -> Result<T>) -> Result<T> {
+    let mut parser = Parser::new(input);
+    let value = f(&mut parser).and_then(|value| {
+        parser.skip_whitespace();
+        parser.end().map(|()| value)
+    });
+    value.map_err(|error| {
+        if parser.furthest.borrow().1.is_empty() {
+            return error;
+        }
+        let furthest = parser.furthest_error();
+        if (furthest.line, furthest.column) > (error.line, error.column) {
+            furthest
+        } else {
+            error
+        }
+    })
+}
+
+/// `Parser` is a position in the input. Every method either consumes what it parsed and returns
+/// `Ok`, or returns an [`Error`] and leaves the position alone.
+#[derive(Debug, Clone)]
+pub struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The position and expectation of the error furthest into the input so far
+    furthest: RefCell<(usize, String)>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            furthest: RefCell::new((0, String::new())),
+        }
+    }
+
+    /// The input that has not been parsed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> Error {
+        let expected = expected.into();
+        let mut furthest = self.furthest.borrow_mut();
+        if self.pos > furthest.0 || furthest.1.is_empty() {
+            *furthest = (self.pos, expected.clone());
+        }
+        Error::at(self.input, self.pos, expected)
+    }
+
+    /// The error that got furthest into the input, see [`parse`].
+    pub fn furthest_error(&self) -> Error {
+        let (pos, expected) = self.furthest.borrow().clone();
+        Error::at(self.input, pos, expected)
+    }
+
+    /// Requires the end of the input.
+    pub fn end(&self) -> Result<()> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("end of input"))
+        }
+    }
+
+    /// Consumes the longest prefix whose characters all satisfy `pred`, which may be empty.
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Skips spaces and tabs, but not line breaks.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Consumes `literal` if the input continues with it.
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    /// Requires the input to continue with `literal`.
+    pub fn literal(&mut self, literal: &str) -> Result<()> {
+        if self.try_literal(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("{literal:?}")))
+        }
+    }
+
+    /// Requires a line break, `\n` or `\r\n`.
+    pub fn newline(&mut self) -> Result<()> {
+        if self.try_literal("\n") || self.try_literal("\r\n") {
+            Ok(())
+        } else {
+            Err(self.error("line break"))
+        }
+    }
+
+    /// Requires an empty line, which is two line breaks in a row.
+    pub fn blank_line(&mut self) -> Result<()> {
+        self.attempt(|p| {
+            p.newline()?;
+            p.newline()
+        })
+    }
+
+    /// Parses an unsigned integer, digits only.
+    ///
+    /// ```
+    /// use aoc::parse::Parser;
+    ///
+    /// let mut p = Parser::new("300 -1");
+    /// assert_eq!(p.uint::<u8>().unwrap_err().expected, "integer that fits into u8");
+    /// assert_eq!(p.uint::<u16>(), Ok(300));
+    /// p.literal(" ").unwrap();
+    /// assert!(p.uint::<u32>().is_err());
+    /// assert_eq!(p.int::<i8>(), Ok(-1));
+    /// ```
+    pub fn uint<T: FromStr>(&mut self) -> Result<T> {
+        let digits = self.rest().find(|c: char| !c.is_ascii_digit());
+        let len = digits.unwrap_or(self.rest().len());
+        self.number(len)
+    }
+
+    /// Parses an integer with an optional `+` or `-` sign.
+    pub fn int<T: FromStr>(&mut self) -> Result<T> {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with(['+', '-']));
+        let digits = rest[sign..].find(|c: char| !c.is_ascii_digit());
+        let len = digits.unwrap_or(rest.len() - sign);
+        if len == 0 {
+            return Err(self.error("integer"));
+        }
+        self.number(sign + len)
+    }
+
+    fn number<T: FromStr>(&mut self, len: usize) -> Result<T> {
+        if len == 0 {
+            return Err(self.error("integer"));
+        }
+        let n = self.rest()[..len].parse().map_err(|_| {
+            let name = std::any::type_name::<T>();
+            self.error(format!("integer that fits into {name}"))
+        })?;
+        self.pos += len;
+        Ok(n)
+    }
+
+    /// Parses an identifier: a letter or `_`, then letters, digits and `_`.
+    pub fn ident(&mut self) -> Result<&'a str> {
+        let rest = self.rest();
+        if !rest.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Err(self.error("identifier"));
+        }
+        Ok(self.take_while(|c| c.is_alphanumeric() || c == '_'))
+    }
+
+    /// Runs `f`, and goes back to where it started if it fails.
+    pub fn attempt<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let start = self.pos;
+        f(self).inspect_err(|_| self.pos = start)
+    }
+
+    /// Runs `f`, and fails at the start of what it parsed if `valid` rejects the value.
+    ///
+    /// ```
+    /// use aoc::parse::Parser;
+    ///
+    /// let mut p = Parser::new("9");
+    /// let error = p.verify(|p| p.uint::<u8>(), |&n| n < 8, "3-bit number").unwrap_err();
+    /// assert_eq!((error.column, error.expected.as_str()), (1, "3-bit number"));
+    /// assert_eq!(p.rest(), "9");
+    /// ```
+    pub fn verify<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+        valid: impl FnOnce(&T) -> bool,
+        expected: impl Into<String>,
+    ) -> Result<T> {
+        let start = self.pos;
+        let value = self.attempt(f)?;
+        if valid(&value) {
+            Ok(value)
+        } else {
+            self.pos = start;
+            Err(self.error(expected))
+        }
+    }
+
+    /// Parses items with `f` for as long as that works, possibly none.
+    pub fn many<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Vec<T> {
+        let mut items = vec![];
+        while let Ok(item) = self.attempt(&mut f) {
+            items.push(item);
+        }
+        items
+    }
+
+    /// Parses at least one item with `f`, then as many as possible.
+    pub fn many1<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let first = self.attempt(&mut f)?;
+        let mut items = vec![first];
+        items.extend(self.many(f));
+        Ok(items)
+    }
+
+    /// Parses one or more items with `f`, with `separator` between them.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        f: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        self.separated_by(|p| p.literal(separator), f)
+    }
+
+    /// Parses one item per line with `f`, up to a blank line or the end of the input. If not even
+    /// the first line parses, nothing is consumed.
+    ///
+    /// ```
+    /// use aoc::parse::Parser;
+    ///
+    /// let mut p = Parser::new("12,x\n3,4");
+    /// let pairs = p.lines(|p| {
+    ///     let a = p.uint::<u32>()?;
+    ///     p.literal(",")?;
+    ///     Ok((a, p.uint::<u32>()?))
+    /// });
+    /// assert!(pairs.is_err());
+    /// assert_eq!(p.rest(), "12,x\n3,4");
+    /// ```
+    pub fn lines<T>(&mut self, f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        self.separated_by(|p| p.newline(), f)
+    }
+
+    /// Parses blocks separated by blank lines with `f`.
+    ///
+    /// ```
+    /// use aoc::parse::{self, Parser};
+    ///
+    /// let input = "1000\n2000\n\n4000\r\n\r\n5000\n6000\n";
+    /// let elves = parse::parse(input, |p: &mut Parser| {
+    ///     p.sections(|p| p.lines(|p| p.uint::<u32>()))
+    /// });
+    /// assert_eq!(elves, Ok(vec![vec![1000, 2000], vec![4000], vec![5000, 6000]]));
+    /// ```
+    pub fn sections<T>(&mut self, f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        self.separated_by(|p| p.blank_line(), f)
+    }
+
+    /// Parses one or more items with `f`, where `separator` parses what is between them. A
+    /// separator that is not followed by another item is left unparsed.
+    pub fn separated_by<T>(
+        &mut self,
+        mut separator: impl FnMut(&mut Self) -> Result<()>,
+        mut f: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = vec![self.attempt(&mut f)?];
+        loop {
+            let start = self.pos;
+            match separator(self).and_then(|()| f(self)) {
+                Ok(item) => items.push(item),
+                Err(_) => {
+                    self.pos = start;
+                    return Ok(items);
+                }
+            }
+        }
+    }
+}
+
+/// `Scan` is a value that [`scan!`](crate::scan!) can parse from a whole field.
+///
+/// ```
+/// use aoc::scan;
+///
+/// let line = "Button A: X+94, Y+34";
+/// let (button, x, y) = scan!(line, "Button {}: X+{}, Y+{}" => char, i64, i64).unwrap();
+/// assert_eq!((button, x, y), ('A', 94, 34));
+///
+/// let line = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB";
+/// let (valve, rate, tunnels) =
+///     scan!(line, "Valve {} has flow rate={}; tunnels lead to valves {, }" => &str, u32, &str)
+///         .unwrap();
+/// assert_eq!((valve, rate), ("AA", 0));
+/// assert!(tunnels.eq(["DD", "II", "BB"]));
+///
+/// let error = scan!("p=0,4 v=3,x", "p={},{} v={},{}" => i32, i32, i32, i32).unwrap_err();
+/// assert_eq!(error.to_string(), "line 1, column 11: expected i32 for placeholder 4");
+/// ```
+pub trait Scan<'a>: Sized {
+    fn scan(field: &'a str) -> Option<Self>;
+}
+
+impl<'a> Scan<'a> for &'a str {
+    fn scan(field: &'a str) -> Option<Self> {
+        Some(field)
+    }
+}
+
+impl Scan<'_> for char {
+    fn scan(field: &str) -> Option<Self> {
+        let mut chars = field.chars();
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+}
+
+macro_rules! scan_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl Scan<'_> for $t {
+                fn scan(field: &str) -> Option<Self> {
+                    field.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+scan_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, String
+);
+
+/// `List` iterates over the values of a `{sep}` placeholder of [`scan!`](crate::scan!), parsing
+/// them on the fly. Every value was checked when the list was created.
+#[derive(Debug, Clone, Copy)]
+pub struct List<'a, T> {
+    rest: Option<&'a str>,
+    separator: &'a str,
+    item: PhantomData<T>,
+}
+
+impl<'a, T: Scan<'a>> List<'a, T> {
+    /// The list of values in `field`, or `None` if one of them does not parse. An empty field is
+    /// an empty list.
+    pub fn new(field: &'a str, separator: &'a str) -> Option<Self> {
+        let list = Self {
+            rest: (!field.is_empty()).then_some(field),
+            separator,
+            item: PhantomData,
+        };
+        list.raw()
+            .all(|item| T::scan(item).is_some())
+            .then_some(list)
+    }
+
+    fn raw(&self) -> impl Iterator<Item = &'a str> + use<'a, T> {
+        let separator = self.separator;
+        self.rest
+            .into_iter()
+            .flat_map(move |rest| rest.split(separator))
+    }
+}
+
+impl<'a, T: Scan<'a>> Iterator for List<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let rest = self.rest?;
+        let (item, rest) = match rest.split_once(self.separator) {
+            Some((item, rest)) => (item, Some(rest)),
+            None => (rest, None),
+        };
+        self.rest = rest;
+        T::scan(item)
+    }
+}
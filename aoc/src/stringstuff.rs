@@ -69,3 +69,167 @@ impl CharExt for char {
         }
     }
 }
+
+// Pulling numbers and blocks out of text
+// --------------------------------------
+
+/// Every integer in `s`, in order, ignoring whatever is between them.
+///
+/// A `-` right before the digits is a minus sign, unless it follows a letter or digit. Then it is
+/// a hyphen or a range, as in `3-5` or `x-2`.
+///
+/// # Panics
+///
+/// If an integer does not fit into `T`.
+///
+/// ```
+/// use aoc::stringstuff::ints;
+///
+/// assert!(ints::<i32>("p=0,4 v=3,-3").eq([0, 4, 3, -3]));
+/// assert!(ints::<i64>("2-4,6--8").eq([2, 4, 6, -8]));
+/// assert!(ints::<i32>("Valve x-2 at -1").eq([2, -1]));
+/// ```
+pub fn ints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    numbers(s, true)
+}
+
+/// Every unsigned integer in `s`, in order. A `-` is never a sign, so `3-5` is `3` and `5`.
+///
+/// # Panics
+///
+/// If an integer does not fit into `T`.
+///
+/// ```
+/// use aoc::stringstuff::uints;
+///
+/// assert!(uints::<u64>("Game 12: 3 blue, 4 red").eq([12, 3, 4]));
+/// assert!(uints::<u8>("10-20").eq([10, 20]));
+/// assert!(uints::<u8>("no numbers").next().is_none());
+/// ```
+pub fn uints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    numbers(s, false)
+}
+
+fn numbers<T>(s: &str, signed: bool) -> impl Iterator<Item = T> + '_
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+
+        let mut start = i;
+        let minus = start > 0 && bytes[start - 1] == b'-';
+        let after_word = start > 1 && bytes[start - 2].is_ascii_alphanumeric();
+        if signed && minus && !after_word {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let number = &s[start..i];
+        Some(
+            number
+                .parse()
+                .unwrap_or_else(|e| panic!("Cannot parse {number:?}: {e:?}")),
+        )
+    })
+}
+
+/// Exactly `N` integers from `s`, see [`ints`].
+///
+/// # Panics
+///
+/// If `s` does not contain exactly `N` integers, or one does not fit into `T`.
+///
+/// ```
+/// use aoc::stringstuff::ints_n;
+///
+/// let [destination, source, len] = ints_n::<u64, 3>("50 98 2");
+/// assert_eq!((destination, source, len), (50, 98, 2));
+/// ```
+pub fn ints_n<T, const N: usize>(s: &str) -> [T; N]
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    let numbers: Vec<T> = ints(s).collect();
+    let found = numbers.len();
+    numbers
+        .try_into()
+        .unwrap_or_else(|_| panic!("Expected {N} integers in {s:?}, found {found}"))
+}
+
+/// The blocks of `input` separated by blank lines, without their line breaks at the end.
+///
+/// Works with `\n` and `\r\n` line breaks, and ignores blank lines at the start and end, or several
+/// in a row.
+///
+/// ```
+/// use aoc::stringstuff::sections;
+///
+/// let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\n";
+/// assert!(sections(input).eq(["seeds: 79 14", "seed-to-soil map:\n50 98 2\n52 50 48"]));
+///
+/// let windows = "1000\r\n2000\r\n\r\n\r\n3000\r\n";
+/// assert!(sections(windows).eq(["1000\r\n2000", "3000"]));
+/// ```
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        // Skip blank lines
+        while let Some(line) = rest.split_inclusive('\n').next() {
+            if !line.trim().is_empty() {
+                break;
+            }
+            rest = &rest[line.len()..];
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        // Take lines up to the next blank one
+        let mut len = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                break;
+            }
+            len += line.len();
+        }
+        let section = rest[..len].trim_end_matches(['\n', '\r']);
+        rest = &rest[len..];
+        Some(section)
+    })
+}
+
+/// Splits `line` into fields of `width` bytes each, the last one may be shorter.
+///
+/// ```
+/// use aoc::stringstuff::columns;
+///
+/// // Crates are 3 characters wide, with a space after each
+/// assert!(columns("    [D]    ", 4).eq(["    ", "[D] ", "   "]));
+/// assert!(columns("", 4).next().is_none());
+/// ```
+pub fn columns(line: &str, width: usize) -> impl Iterator<Item = &str> {
+    assert!(width > 0, "Columns need a width");
+    (0..line.len())
+        .step_by(width)
+        .map(move |start| &line[start..(start + width).min(line.len())])
+}
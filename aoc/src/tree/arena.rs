@@ -0,0 +1,766 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// `NodeId` is the handle of a node in a [`Tree`]. It stays valid until the node is removed, after
+/// which the tree may hand it out again for a new node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The index of the node in the arena, for keeping extra data about nodes in a `Vec`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    value: T,
+    parent: Option<NodeId>,
+    first_child: Option<NodeId>,
+    last_child: Option<NodeId>,
+    prev_sibling: Option<NodeId>,
+    next_sibling: Option<NodeId>,
+}
+
+impl<T> Slot<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            parent: None,
+            first_child: None,
+            last_child: None,
+            prev_sibling: None,
+            next_sibling: None,
+        }
+    }
+}
+
+/// `Tree` is a rooted, ordered tree stored in an arena.
+///
+/// Every node knows its parent, its first and last child and its siblings, so all of those are O(1)
+/// and the traversals need no stack. Values are reached by indexing the tree with a [`NodeId`].
+///
+/// ```
+/// use aoc::tree::Tree;
+///
+/// //     1
+/// //    / \
+/// //   2   5
+/// //  / \   \
+/// // 3   4   6
+///
+/// let mut tree = Tree::new(1);
+/// let root = tree.root();
+/// let two = tree.add_child(root, 2);
+/// let three = tree.add_child(two, 3);
+/// let four = tree.add_child(two, 4);
+/// let five = tree.add_child(root, 5);
+/// let six = tree.add_child(five, 6);
+///
+/// assert_eq!(tree.parent(four), Some(two));
+/// assert_eq!(tree.next_sibling(two), Some(five));
+/// assert!(tree.children(two).eq([three, four]));
+/// assert_eq!(tree.depth(six), 2);
+///
+/// tree[four] *= 10;
+/// let values: Vec<i32> = tree.preorder(root).map(|id| tree[id]).collect();
+/// assert_eq!(values, [1, 2, 3, 40, 5, 6]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree<T> {
+    slots: Vec<Option<Slot<T>>>,
+    /// Slots of removed nodes, to be reused
+    free: Vec<NodeId>,
+    root: NodeId,
+    len: usize,
+}
+
+impl<T> Tree<T> {
+    /// A tree with a single node.
+    pub fn new(root: T) -> Self {
+        Self {
+            slots: vec![Some(Slot::new(root))],
+            free: vec![],
+            root: NodeId(0),
+            len: 1,
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// The number of nodes. There is always at least the root.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// `true` if `id` is a node of this tree, and has not been removed.
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.slots.get(id.0).is_some_and(Option::is_some)
+    }
+
+    fn slot(&self, id: NodeId) -> &Slot<T> {
+        self.slots[id.0].as_ref().expect("node has been removed")
+    }
+
+    fn slot_mut(&mut self, id: NodeId) -> &mut Slot<T> {
+        self.slots[id.0].as_mut().expect("node has been removed")
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        Some(&self.slots.get(id.0)?.as_ref()?.value)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        Some(&mut self.slots.get_mut(id.0)?.as_mut()?.value)
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).parent
+    }
+
+    pub fn first_child(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).first_child
+    }
+
+    pub fn last_child(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).last_child
+    }
+
+    pub fn prev_sibling(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).prev_sibling
+    }
+
+    pub fn next_sibling(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).next_sibling
+    }
+
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.slot(id).first_child.is_none()
+    }
+
+    /// The number of edges between `id` and the root.
+    pub fn depth(&self, id: NodeId) -> usize {
+        std::iter::successors(self.parent(id), |&p| self.parent(p)).count()
+    }
+
+    /// The children of `id`, first to last. Runs backwards too.
+    pub fn children(&self, id: NodeId) -> Children<'_, T> {
+        let slot = self.slot(id);
+        Children {
+            tree: self,
+            front: slot.first_child,
+            back: slot.last_child,
+        }
+    }
+
+    fn alloc(&mut self, value: T) -> NodeId {
+        self.len += 1;
+        match self.free.pop() {
+            Some(id) => {
+                self.slots[id.0] = Some(Slot::new(value));
+                id
+            }
+            None => {
+                self.slots.push(Some(Slot::new(value)));
+                NodeId(self.slots.len() - 1)
+            }
+        }
+    }
+
+    /// Links the unattached node `id` in between `prev` and `next` under `parent`.
+    fn link(&mut self, id: NodeId, parent: NodeId, prev: Option<NodeId>, next: Option<NodeId>) {
+        let slot = self.slot_mut(id);
+        slot.parent = Some(parent);
+        slot.prev_sibling = prev;
+        slot.next_sibling = next;
+
+        match prev {
+            Some(prev) => self.slot_mut(prev).next_sibling = Some(id),
+            None => self.slot_mut(parent).first_child = Some(id),
+        }
+        match next {
+            Some(next) => self.slot_mut(next).prev_sibling = Some(id),
+            None => self.slot_mut(parent).last_child = Some(id),
+        }
+    }
+
+    /// Takes `id` out of its parent's list of children, keeping its own subtree.
+    fn unlink(&mut self, id: NodeId) {
+        let slot = self.slot_mut(id);
+        let (parent, prev, next) = (slot.parent.take(), slot.prev_sibling, slot.next_sibling);
+        slot.prev_sibling = None;
+        slot.next_sibling = None;
+        let Some(parent) = parent else {
+            return;
+        };
+
+        match prev {
+            Some(prev) => self.slot_mut(prev).next_sibling = next,
+            None => self.slot_mut(parent).first_child = next,
+        }
+        match next {
+            Some(next) => self.slot_mut(next).prev_sibling = prev,
+            None => self.slot_mut(parent).last_child = prev,
+        }
+    }
+
+    /// Adds `value` as the last child of `parent`.
+    pub fn add_child(&mut self, parent: NodeId, value: T) -> NodeId {
+        let id = self.alloc(value);
+        let last = self.slot(parent).last_child;
+        self.link(id, parent, last, None);
+        id
+    }
+
+    /// Adds `value` as the first child of `parent`.
+    pub fn prepend_child(&mut self, parent: NodeId, value: T) -> NodeId {
+        let id = self.alloc(value);
+        let first = self.slot(parent).first_child;
+        self.link(id, parent, None, first);
+        id
+    }
+
+    /// Adds `value` as the sibling right before `sibling`, which must not be the root.
+    pub fn insert_before(&mut self, sibling: NodeId, value: T) -> NodeId {
+        let parent = self.parent(sibling).expect("the root has no siblings");
+        let id = self.alloc(value);
+        let prev = self.slot(sibling).prev_sibling;
+        self.link(id, parent, prev, Some(sibling));
+        id
+    }
+
+    /// Adds `value` as the sibling right after `sibling`, which must not be the root.
+    pub fn insert_after(&mut self, sibling: NodeId, value: T) -> NodeId {
+        let parent = self.parent(sibling).expect("the root has no siblings");
+        let id = self.alloc(value);
+        let next = self.slot(sibling).next_sibling;
+        self.link(id, parent, Some(sibling), next);
+        id
+    }
+
+    /// Removes `id` and everything below it, returning them as a tree of their own. The ids of the
+    /// removed nodes are no longer valid.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("/");
+    /// let root = tree.root();
+    /// let a = tree.add_child(root, "a");
+    /// let e = tree.add_child(a, "e");
+    /// tree.add_child(e, "i");
+    /// let d = tree.add_child(root, "d");
+    ///
+    /// let removed = tree.remove_subtree(a);
+    /// assert_eq!(tree.len(), 2);
+    /// assert!(!tree.contains(e));
+    /// assert!(tree.children(root).eq([d]));
+    ///
+    /// let names: Vec<_> = removed.preorder(removed.root()).map(|id| removed[id]).collect();
+    /// assert_eq!(names, ["a", "e", "i"]);
+    /// ```
+    pub fn remove_subtree(&mut self, id: NodeId) -> Tree<T> {
+        assert_ne!(id, self.root, "cannot remove the root");
+        self.unlink(id);
+
+        let nodes: Vec<NodeId> = self.preorder(id).collect();
+        self.len -= nodes.len();
+        self.free.extend(&nodes);
+
+        // Parents come before their children in preorder, so they are always copied first
+        let root = self.slots[id.0].take().unwrap();
+        let mut subtree = Tree::new(root.value);
+        let mut new_ids = HashMap::from([(id, subtree.root)]);
+        for &node in &nodes[1..] {
+            let slot = self.slots[node.0].take().unwrap();
+            let parent = new_ids[&slot.parent.unwrap()];
+            new_ids.insert(node, subtree.add_child(parent, slot.value));
+        }
+        subtree
+    }
+
+    /// Removes all children of `id` and their subtrees, making `id` a leaf.
+    pub fn clear_children(&mut self, id: NodeId) {
+        while let Some(child) = self.first_child(id) {
+            self.remove_subtree(child);
+        }
+    }
+
+    /// Splices `tree` in as the last child of `parent`, and returns the id its root got.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut pair = Tree::new('+');
+    /// let mut left = Tree::new('*');
+    /// left.add_child(left.root(), 'a');
+    /// left.add_child(left.root(), 'b');
+    ///
+    /// let root = pair.root();
+    /// let times = pair.append_tree(root, left);
+    /// pair.add_child(root, 'c');
+    ///
+    /// let expression: String = pair.postorder(root).map(|id| pair[id]).collect();
+    /// assert_eq!(expression, "ab*c+");
+    /// assert_eq!(pair.parent(times), Some(root));
+    /// ```
+    pub fn append_tree(&mut self, parent: NodeId, tree: Tree<T>) -> NodeId {
+        let order: Vec<NodeId> = tree.preorder(tree.root).collect();
+        let parents: Vec<Option<NodeId>> = order.iter().map(|&id| tree.parent(id)).collect();
+
+        let mut slots = tree.slots;
+        let mut new_ids = vec![None; slots.len()];
+        for (id, old_parent) in order.into_iter().zip(parents) {
+            let value = slots[id.0].take().unwrap().value;
+            let new_parent = match old_parent {
+                Some(p) => new_ids[p.0].unwrap(),
+                None => parent,
+            };
+            new_ids[id.0] = Some(self.add_child(new_parent, value));
+        }
+        new_ids[tree.root.0].unwrap()
+    }
+
+    /// The nodes of the subtree at `id` in preorder: every node before its children.
+    pub fn preorder(&self, id: NodeId) -> Preorder<'_, T> {
+        Preorder {
+            tree: self,
+            start: id,
+            next: Some(id),
+        }
+    }
+
+    /// The nodes of the subtree at `id` in postorder: every node after its children.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(6);
+    /// let root = tree.root();
+    /// let three = tree.add_child(root, 3);
+    /// tree.add_child(three, 1);
+    /// tree.add_child(three, 2);
+    /// let five = tree.add_child(root, 5);
+    /// tree.add_child(five, 4);
+    ///
+    /// assert!(tree.postorder(root).map(|id| tree[id]).eq(1..=6));
+    /// assert!(tree.postorder(three).map(|id| tree[id]).eq(1..=3));
+    /// ```
+    pub fn postorder(&self, id: NodeId) -> Postorder<'_, T> {
+        Postorder {
+            tree: self,
+            start: id,
+            next: Some(self.leftmost_leaf(id)),
+        }
+    }
+
+    /// The nodes of the subtree at `id` by depth, each level left to right.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(1);
+    /// let root = tree.root();
+    /// let two = tree.add_child(root, 2);
+    /// tree.add_child(two, 4);
+    /// let three = tree.add_child(root, 3);
+    /// tree.add_child(three, 5);
+    ///
+    /// assert!(tree.level_order(root).map(|id| tree[id]).eq(1..=5));
+    /// ```
+    pub fn level_order(&self, id: NodeId) -> LevelOrder<'_, T> {
+        LevelOrder {
+            tree: self,
+            queue: VecDeque::from([id]),
+        }
+    }
+
+    /// The nodes of the subtree at `id` in order: the subtree of the first child, then the node,
+    /// then the subtrees of the other children. For binary trees that is left, node, right.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// //     4
+    /// //    / \
+    /// //   2   5
+    /// //  / \   \
+    /// // 1   3   6
+    ///
+    /// let mut tree = Tree::new(4);
+    /// let root = tree.root();
+    /// let two = tree.add_child(root, 2);
+    /// tree.add_child(two, 1);
+    /// tree.add_child(two, 3);
+    /// let five = tree.add_child(root, 5);
+    /// tree.add_child(five, 6);
+    ///
+    /// assert!(tree.inorder(root).map(|id| tree[id]).eq([1, 2, 3, 4, 6, 5]));
+    /// ```
+    pub fn inorder(&self, id: NodeId) -> Inorder<'_, T> {
+        Inorder {
+            tree: self,
+            stack: vec![(id, false)],
+        }
+    }
+
+    /// Combines the values of the subtree at `id` bottom up: `f` gets the value of a node and the
+    /// results of its children, in order, and the result for `id` is returned.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(1);
+    /// let root = tree.root();
+    /// let two = tree.add_child(root, 2);
+    /// tree.add_child(two, 3);
+    /// tree.add_child(root, 4);
+    ///
+    /// let sum = tree.fold(root, |&value, children: &[i32]| value + children.iter().sum::<i32>());
+    /// assert_eq!(sum, 10);
+    ///
+    /// let height = tree.fold(root, |_, children: &[usize]| {
+    ///     children.iter().max().map_or(0, |h| h + 1)
+    /// });
+    /// assert_eq!(height, 2);
+    /// ```
+    pub fn fold<A>(&self, id: NodeId, f: impl FnMut(&T, &[A]) -> A) -> A {
+        let mut results = self.fold_with(id, f, Option::take);
+        results[id.0].take().unwrap()
+    }
+
+    /// Like [`Tree::fold`], but keeps the result of every node, indexed by [`NodeId::index`].
+    /// Nodes outside the subtree at `id` have no result.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new(("/", 0));
+    /// let root = tree.root();
+    /// let a = tree.add_child(root, ("a", 0));
+    /// tree.add_child(a, ("f", 29116));
+    /// tree.add_child(root, ("b.txt", 14848514));
+    ///
+    /// let sizes = tree.fold_all(root, |&(_, size), children: &[u64]| {
+    ///     size + children.iter().sum::<u64>()
+    /// });
+    /// assert_eq!(sizes[a.index()], Some(29116));
+    /// assert_eq!(sizes[root.index()], Some(14877630));
+    /// ```
+    pub fn fold_all<A: Clone>(&self, id: NodeId, f: impl FnMut(&T, &[A]) -> A) -> Vec<Option<A>> {
+        self.fold_with(id, f, |result| result.clone())
+    }
+
+    /// Folds in postorder, getting the result of a child out of `results` with `get`.
+    fn fold_with<A>(
+        &self,
+        id: NodeId,
+        mut f: impl FnMut(&T, &[A]) -> A,
+        get: impl Fn(&mut Option<A>) -> Option<A>,
+    ) -> Vec<Option<A>> {
+        let mut results: Vec<Option<A>> = std::iter::repeat_with(|| None)
+            .take(self.slots.len())
+            .collect();
+        let mut children = vec![];
+        for node in self.postorder(id) {
+            children.clear();
+            children.extend(
+                self.children(node)
+                    .map(|child| get(&mut results[child.0]).unwrap()),
+            );
+            results[node.0] = Some(f(&self[node], &children));
+        }
+        results
+    }
+
+    /// The parent of `id`, its parent, and so on up to the root.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |&p| self.parent(p))
+    }
+
+    /// The nodes on the way from the root down to `id`, both included.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("/");
+    /// let root = tree.root();
+    /// let a = tree.add_child(root, "a");
+    /// let e = tree.add_child(a, "e");
+    ///
+    /// let path: Vec<_> = tree.path_from_root(e).into_iter().map(|id| tree[id]).collect();
+    /// assert_eq!(path, ["/", "a", "e"]);
+    /// ```
+    pub fn path_from_root(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path: Vec<NodeId> = std::iter::once(id).chain(self.ancestors(id)).collect();
+        path.reverse();
+        path
+    }
+
+    /// The lowest common ancestor of `a` and `b`: the deepest node that has both in its subtree.
+    /// A node counts as being in its own subtree.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("COM");
+    /// let com = tree.root();
+    /// let b = tree.add_child(com, "B");
+    /// let c = tree.add_child(b, "C");
+    /// let you = tree.add_child(c, "YOU");
+    /// let san = tree.add_child(b, "SAN");
+    ///
+    /// assert_eq!(tree.lca(you, san), b);
+    /// assert_eq!(tree.lca(c, you), c);
+    /// assert_eq!(tree.depth(you) + tree.depth(san) - 2 * tree.depth(b), 3);
+    /// ```
+    pub fn lca(&self, a: NodeId, b: NodeId) -> NodeId {
+        let (mut a, mut b) = (a, b);
+        let (mut depth_a, mut depth_b) = (self.depth(a), self.depth(b));
+        while depth_a > depth_b {
+            a = self.parent(a).unwrap();
+            depth_a -= 1;
+        }
+        while depth_b > depth_a {
+            b = self.parent(b).unwrap();
+            depth_b -= 1;
+        }
+        while a != b {
+            a = self.parent(a).unwrap();
+            b = self.parent(b).unwrap();
+        }
+        a
+    }
+
+    /// The closest leaf before the subtree at `id`, in the order leaves are drawn left to right.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// // [[1,[2,3]],4]
+    /// let mut tree = Tree::new(None);
+    /// let root = tree.root();
+    /// let left = tree.add_child(root, None);
+    /// let one = tree.add_child(left, Some(1));
+    /// let pair = tree.add_child(left, None);
+    /// let two = tree.add_child(pair, Some(2));
+    /// let three = tree.add_child(pair, Some(3));
+    /// let four = tree.add_child(root, Some(4));
+    ///
+    /// assert_eq!(tree.prev_leaf(pair), Some(one));
+    /// assert_eq!(tree.next_leaf(pair), Some(four));
+    /// assert_eq!(tree.prev_leaf(four), Some(three));
+    /// assert_eq!(tree.next_leaf(one), Some(two));
+    /// assert_eq!(tree.prev_leaf(one), None);
+    /// ```
+    pub fn prev_leaf(&self, id: NodeId) -> Option<NodeId> {
+        let sibling = std::iter::once(id)
+            .chain(self.ancestors(id))
+            .find_map(|node| self.prev_sibling(node))?;
+        Some(self.rightmost_leaf(sibling))
+    }
+
+    /// The closest leaf after the subtree at `id`, see [`Tree::prev_leaf`].
+    pub fn next_leaf(&self, id: NodeId) -> Option<NodeId> {
+        let sibling = std::iter::once(id)
+            .chain(self.ancestors(id))
+            .find_map(|node| self.next_sibling(node))?;
+        Some(self.leftmost_leaf(sibling))
+    }
+
+    fn leftmost_leaf(&self, mut id: NodeId) -> NodeId {
+        while let Some(child) = self.first_child(id) {
+            id = child;
+        }
+        id
+    }
+
+    fn rightmost_leaf(&self, mut id: NodeId) -> NodeId {
+        while let Some(child) = self.last_child(id) {
+            id = child;
+        }
+        id
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Tree<T> {
+    /// Draws the tree like the `tree` command.
+    ///
+    /// ```
+    /// use aoc::tree::Tree;
+    ///
+    /// let mut tree = Tree::new("/");
+    /// let root = tree.root();
+    /// let a = tree.add_child(root, "a");
+    /// tree.add_child(a, "e");
+    /// tree.add_child(a, "f");
+    /// let d = tree.add_child(root, "d");
+    /// tree.add_child(d, "j");
+    ///
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "/\n├── a\n│   ├── e\n│   └── f\n└── d\n    └── j\n"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        super::draw(f, self.root, &|f, &id| write!(f, "{}", self[id]), &|&id| {
+            self.children(id).collect()
+        })
+    }
+}
+
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        &self.slot(id).value
+    }
+}
+
+impl<T> IndexMut<NodeId> for Tree<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.slot_mut(id).value
+    }
+}
+
+/// The iterator returned by [`Tree::children`].
+#[derive(Debug, Clone)]
+pub struct Children<'a, T> {
+    tree: &'a Tree<T>,
+    front: Option<NodeId>,
+    back: Option<NodeId>,
+}
+
+impl<T> Iterator for Children<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.front?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = self.tree.next_sibling(id);
+        }
+        Some(id)
+    }
+}
+
+impl<T> DoubleEndedIterator for Children<'_, T> {
+    fn next_back(&mut self) -> Option<NodeId> {
+        let id = self.back?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = self.tree.prev_sibling(id);
+        }
+        Some(id)
+    }
+}
+
+/// The iterator returned by [`Tree::preorder`].
+#[derive(Debug, Clone)]
+pub struct Preorder<'a, T> {
+    tree: &'a Tree<T>,
+    start: NodeId,
+    next: Option<NodeId>,
+}
+
+impl<T> Iterator for Preorder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.next?;
+        self.next = self.tree.first_child(id).or_else(|| {
+            // Climb until there is a sibling to go to, without leaving the subtree
+            let mut node = id;
+            loop {
+                if node == self.start {
+                    return None;
+                }
+                if let Some(sibling) = self.tree.next_sibling(node) {
+                    return Some(sibling);
+                }
+                node = self.tree.parent(node)?;
+            }
+        });
+        Some(id)
+    }
+}
+
+/// The iterator returned by [`Tree::postorder`].
+#[derive(Debug, Clone)]
+pub struct Postorder<'a, T> {
+    tree: &'a Tree<T>,
+    start: NodeId,
+    next: Option<NodeId>,
+}
+
+impl<T> Iterator for Postorder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.next?;
+        self.next = if id == self.start {
+            None
+        } else {
+            match self.tree.next_sibling(id) {
+                Some(sibling) => Some(self.tree.leftmost_leaf(sibling)),
+                None => self.tree.parent(id),
+            }
+        };
+        Some(id)
+    }
+}
+
+/// The iterator returned by [`Tree::level_order`].
+#[derive(Debug, Clone)]
+pub struct LevelOrder<'a, T> {
+    tree: &'a Tree<T>,
+    queue: VecDeque<NodeId>,
+}
+
+impl<T> Iterator for LevelOrder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.queue.pop_front()?;
+        self.queue.extend(self.tree.children(id));
+        Some(id)
+    }
+}
+
+/// The iterator returned by [`Tree::inorder`].
+#[derive(Debug, Clone)]
+pub struct Inorder<'a, T> {
+    tree: &'a Tree<T>,
+    /// Nodes still to visit, and whether their first child has been visited already
+    stack: Vec<(NodeId, bool)>,
+}
+
+impl<T> Iterator for Inorder<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        loop {
+            let (id, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(id);
+            }
+            let mut children = self.tree.children(id);
+            let Some(first) = children.next() else {
+                return Some(id);
+            };
+            self.stack
+                .extend(children.rev().map(|child| (child, false)));
+            self.stack.push((id, true));
+            self.stack.push((first, false));
+        }
+    }
+}
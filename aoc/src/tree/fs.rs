@@ -0,0 +1,268 @@
+//! A file system rebuilt from a shell session, as in "No Space Left On Device".
+//!
+//! The session is a list of `$ cd dir`, `$ cd ..`, `$ cd /` and `$ ls` commands, each `ls` followed
+//! by its output of `dir name` and `size name` lines.
+use std::fmt;
+
+use super::{NodeId, Tree};
+use crate::error::{self, parse_lines, ParseError};
+
+/// `Entry` is a file or a directory in a [`FileSystem`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    /// The size of a file, `None` for a directory
+    pub size: Option<u64>,
+}
+
+impl Entry {
+    pub fn is_dir(&self) -> bool {
+        self.size.is_none()
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.size {
+            Some(size) => write!(f, "{} (file, size={size})", self.name),
+            None => write!(f, "{} (dir)", self.name),
+        }
+    }
+}
+
+/// `FileSystem` is a tree of directories and files that can be addressed by path.
+///
+/// ```
+/// use aoc::tree::fs::FileSystem;
+///
+/// let session = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\ndir e\n29116 f\n$ cd e\n$ ls\n584 i";
+/// let fs = FileSystem::from_transcript(session).unwrap();
+///
+/// let e = fs.lookup("/a/e").unwrap();
+/// assert_eq!(fs.path(e), "/a/e");
+/// assert_eq!(fs.total_size(fs.lookup("/a").unwrap()), 29700);
+/// assert_eq!(fs.total_size(fs.root()), 14878214);
+/// assert_eq!(fs.lookup("/b.txt").and_then(|b| fs.entry(b).size), Some(14848514));
+/// assert_eq!(fs.lookup("/a/x"), None);
+///
+/// assert_eq!(
+///     fs.to_string(),
+///     "/ (dir)\n├── a (dir)\n│   ├── e (dir)\n│   │   └── i (file, size=584)\n│   └── f (file, size=29116)\n└── b.txt (file, size=14848514)\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    tree: Tree<Entry>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem {
+    /// An empty file system, with only the root directory `/`.
+    pub fn new() -> Self {
+        Self {
+            tree: Tree::new(Entry {
+                name: "/".to_string(),
+                size: None,
+            }),
+        }
+    }
+
+    /// Replays a shell session. Directories that are `cd`'d into before being listed are created
+    /// on the way, and listing a directory twice does not add its entries twice.
+    ///
+    /// ```
+    /// use aoc::tree::fs::FileSystem;
+    ///
+    /// let error = FileSystem::from_transcript("$ cd /\n$ ls\n12k a.txt").unwrap_err();
+    /// assert_eq!(error.to_string(), "line 3, column 1: expected file size or \"dir\"");
+    ///
+    /// let error = FileSystem::from_transcript("$ cd ..").unwrap_err();
+    /// assert_eq!(error.to_string(), "line 1, column 6: expected directory below the root");
+    ///
+    /// let error = FileSystem::from_transcript("$ ls\n12 a.txt\n$ cd a.txt").unwrap_err();
+    /// assert_eq!(error.to_string(), "line 3, column 6: expected directory");
+    /// ```
+    pub fn from_transcript(transcript: &str) -> error::Result<Self> {
+        let mut fs = Self::new();
+        let mut cwd = fs.root();
+        parse_lines(transcript, |line| {
+            let error = |pos: usize, expected: &str| ParseError::at(line, pos, expected);
+
+            if let Some(command) = line.strip_prefix("$ ") {
+                if let Some(dir) = command.strip_prefix("cd ") {
+                    cwd = match dir {
+                        "/" => fs.root(),
+                        ".." => fs
+                            .tree
+                            .parent(cwd)
+                            .ok_or_else(|| error(5, "directory below the root"))?,
+                        name => {
+                            let dir = fs.add(cwd, name, None);
+                            if !fs.entry(dir).is_dir() {
+                                return Err(error(5, "directory"));
+                            }
+                            dir
+                        }
+                    };
+                } else if command != "ls" {
+                    return Err(error(2, "\"cd\" or \"ls\""));
+                }
+                return Ok(());
+            }
+
+            let (kind, name) = line
+                .split_once(' ')
+                .ok_or_else(|| error(0, "\"dir name\" or \"size name\""))?;
+            let size = match kind {
+                "dir" => None,
+                size => Some(size.parse().map_err(|_| error(0, "file size or \"dir\""))?),
+            };
+            fs.add(cwd, name, size);
+            Ok(())
+        })?;
+        Ok(fs)
+    }
+
+    /// Adds an entry to `dir` and returns it, or returns the one already there with that name.
+    pub fn add(&mut self, dir: NodeId, name: &str, size: Option<u64>) -> NodeId {
+        match self.child(dir, name) {
+            Some(existing) => existing,
+            None => self.tree.add_child(
+                dir,
+                Entry {
+                    name: name.to_string(),
+                    size,
+                },
+            ),
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.tree.root()
+    }
+
+    pub fn entry(&self, id: NodeId) -> &Entry {
+        &self.tree[id]
+    }
+
+    /// The underlying tree, for traversals.
+    pub fn tree(&self) -> &Tree<Entry> {
+        &self.tree
+    }
+
+    /// The entry called `name` in `dir`.
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.tree
+            .children(dir)
+            .find(|&child| self.tree[child].name == name)
+    }
+
+    /// The entry at an absolute path like `/a/e/i`.
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self.root(), |dir, name| self.child(dir, name))
+    }
+
+    /// The absolute path of `id`.
+    pub fn path(&self, id: NodeId) -> String {
+        if id == self.root() {
+            return "/".to_string();
+        }
+        self.tree
+            .path_from_root(id)
+            .into_iter()
+            .skip(1)
+            .map(|node| format!("/{}", self.tree[node].name))
+            .collect()
+    }
+
+    /// The size of a file, or the size of everything below a directory.
+    pub fn total_size(&self, id: NodeId) -> u64 {
+        self.tree.fold(id, |entry, children: &[u64]| {
+            entry.size.unwrap_or(0) + children.iter().sum::<u64>()
+        })
+    }
+
+    /// Every directory with the size of everything below it, in preorder.
+    pub fn dir_sizes(&self) -> Vec<(NodeId, u64)> {
+        let root = self.root();
+        let sizes = self.tree.fold_all(root, |entry, children: &[u64]| {
+            entry.size.unwrap_or(0) + children.iter().sum::<u64>()
+        });
+        self.tree
+            .preorder(root)
+            .filter(|&id| self.tree[id].is_dir())
+            .map(|id| (id, sizes[id.index()].unwrap()))
+            .collect()
+    }
+
+    /// The smallest directory that frees up at least `needed` when deleted, with its size.
+    ///
+    /// ```
+    /// use aoc::tree::fs::FileSystem;
+    ///
+    /// let session = "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\n100 x\n$ cd ..\n$ cd b\n$ ls\n300 y";
+    /// let fs = FileSystem::from_transcript(session).unwrap();
+    ///
+    /// let (dir, size) = fs.smallest_dir_freeing(200).unwrap();
+    /// assert_eq!((fs.path(dir).as_str(), size), ("/b", 300));
+    /// assert_eq!(fs.smallest_dir_freeing(500), None);
+    /// ```
+    pub fn smallest_dir_freeing(&self, needed: u64) -> Option<(NodeId, u64)> {
+        self.dir_sizes()
+            .into_iter()
+            .filter(|&(_, size)| size >= needed)
+            .min_by_key(|&(_, size)| size)
+    }
+
+    /// A shell session that rebuilds this file system: every directory is listed once, and entered
+    /// and left again in order.
+    ///
+    /// ```
+    /// use aoc::tree::fs::FileSystem;
+    ///
+    /// let session = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n29116 f\n$ cd ..\n";
+    /// let fs = FileSystem::from_transcript(session).unwrap();
+    /// assert_eq!(fs.to_transcript(), session);
+    ///
+    /// let again = FileSystem::from_transcript(&fs.to_transcript()).unwrap();
+    /// assert_eq!(again.to_string(), fs.to_string());
+    /// ```
+    pub fn to_transcript(&self) -> String {
+        let mut transcript = "$ cd /\n".to_string();
+        self.write_transcript(self.root(), &mut transcript);
+        transcript
+    }
+
+    fn write_transcript(&self, dir: NodeId, transcript: &mut String) {
+        transcript.push_str("$ ls\n");
+        for child in self.tree.children(dir) {
+            let entry = &self.tree[child];
+            match entry.size {
+                Some(size) => transcript.push_str(&format!("{size} {}\n", entry.name)),
+                None => transcript.push_str(&format!("dir {}\n", entry.name)),
+            }
+        }
+        for child in self.tree.children(dir) {
+            if self.tree[child].is_dir() {
+                transcript.push_str(&format!("$ cd {}\n", self.tree[child].name));
+                self.write_transcript(child, transcript);
+                transcript.push_str("$ cd ..\n");
+            }
+        }
+    }
+}
+
+impl fmt::Display for FileSystem {
+    /// Draws the file system the way the puzzle does, with the sizes of files.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.tree, f)
+    }
+}
@@ -0,0 +1,53 @@
+//! Trees with parent pointers.
+//!
+//! [`Tree`] keeps all nodes in one `Vec` and refers to them by [`NodeId`], so moving around the tree
+//! is an index lookup and values can be changed in place through `&mut Tree`. The reference counted
+//! tree in [`rc`] is kept for code that already uses it, and is still what `aoc::Tree` and
+//! `aoc::Node` refer to.
+//!
+//! [`fs::FileSystem`] builds a directory tree out of a shell session.
+mod arena;
+pub mod fs;
+pub mod rc;
+
+use std::fmt;
+
+pub use arena::{Children, Inorder, LevelOrder, NodeId, Postorder, Preorder, Tree};
+
+type Label<'a, N> = dyn Fn(&mut fmt::Formatter<'_>, &N) -> fmt::Result + 'a;
+
+/// Draws the tree below `root` like the `tree` command, one node per line with box drawing lines
+/// to its children.
+fn draw<N>(
+    f: &mut fmt::Formatter<'_>,
+    root: N,
+    label: &Label<'_, N>,
+    children: &dyn Fn(&N) -> Vec<N>,
+) -> fmt::Result {
+    label(f, &root)?;
+    writeln!(f)?;
+    draw_children(f, &root, &mut String::new(), label, children)
+}
+
+fn draw_children<N>(
+    f: &mut fmt::Formatter<'_>,
+    node: &N,
+    prefix: &mut String,
+    label: &Label<'_, N>,
+    children: &dyn Fn(&N) -> Vec<N>,
+) -> fmt::Result {
+    let children_of_node = children(node);
+    let count = children_of_node.len();
+    for (i, child) in children_of_node.into_iter().enumerate() {
+        let last = i + 1 == count;
+        write!(f, "{prefix}{}", if last { "└── " } else { "├── " })?;
+        label(f, &child)?;
+        writeln!(f)?;
+
+        let len = prefix.len();
+        prefix.push_str(if last { "    " } else { "│   " });
+        draw_children(f, &child, prefix, label, children)?;
+        prefix.truncate(len);
+    }
+    Ok(())
+}
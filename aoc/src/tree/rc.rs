@@ -1,4 +1,6 @@
-//! This is a tree data structure with backpointers. It's a helpful construction from time to time.
+//! The original tree, built from reference counted nodes with back pointers. Nodes are handles that
+//! can be cloned and kept around, but every access goes through a `RefCell`. New code should prefer
+//! the arena backed [`Tree`](super::Tree).
 use std::{
     cell::RefCell,
     rc::{Rc, Weak},
@@ -33,7 +35,7 @@ impl<T> Tree<T> {
     /// This uses O(depth(tree)) additional memory.
     ///
     /// ```
-    /// use aoc::tree::Tree;
+    /// use aoc::tree::rc::Tree;
     ///
     /// //     1
     /// //    / \
@@ -68,7 +70,7 @@ impl<T> Tree<T> {
     /// See [`Tree::iter_preorder`] for more information.
     ///
     /// ```
-    /// use aoc::tree::Tree;
+    /// use aoc::tree::rc::Tree;
     ///
     /// //     1
     /// //    / \
@@ -114,7 +116,7 @@ impl<T> Tree<T> {
     /// additional memory to create the iterator.
     ///
     /// ```
-    /// use aoc::tree::Tree;
+    /// use aoc::tree::rc::Tree;
     ///
     /// //     6
     /// //    / \
@@ -148,7 +150,7 @@ impl<T> Tree<T> {
     /// See [`Tree::iter_postorder`] for more information.
     ///
     /// ```
-    /// use aoc::tree::Tree;
+    /// use aoc::tree::rc::Tree;
     ///
     /// //     6
     /// //    / \
@@ -190,18 +192,72 @@ impl<T> Tree<T> {
         output.into_iter().rev()
     }
 
-    // TODO: iter_inorder, iter_inorder_depth
+    /// Iterate over all nodes in the tree, in order: the subtree of the first child, then the
+    /// node, then the subtrees of the other children.
+    ///
+    /// This uses O(depth(tree)) additional memory.
+    ///
+    /// ```
+    /// use aoc::tree::rc::Tree;
+    ///
+    /// //     4
+    /// //    / \
+    /// //   2   5
+    /// //  / \   \
+    /// // 1   3   6
+    ///
+    /// let mut tree : Tree<i32> = Tree::new(4);
+    /// let mut root = tree.root();
+    /// let mut two = root.add_child(2);
+    /// let one = two.add_child(1);
+    /// let three = two.add_child(3);
+    /// let mut five = root.add_child(5);
+    /// let six = five.add_child(6);
+    ///
+    /// let mut iter = tree.iter_inorder();
+    /// assert_eq!(iter.next(), Some(one));
+    /// assert_eq!(iter.next(), Some(two));
+    /// assert_eq!(iter.next(), Some(three));
+    /// assert_eq!(iter.next(), Some(root));
+    /// assert_eq!(iter.next(), Some(six));
+    /// assert_eq!(iter.next(), Some(five));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter_inorder(&self) -> impl Iterator<Item = Node<T>> {
+        self.iter_inorder_depth().map(|(node, _)| node)
+    }
+
+    /// Iterate over all nodes in the tree, in order tracking the depth of each node.
+    ///
+    /// See [`Tree::iter_inorder`] for more information.
+    pub fn iter_inorder_depth(&self) -> impl Iterator<Item = (Node<T>, usize)> {
+        // The flag says whether the first child has been visited already
+        let mut stack = vec![(self.root(), 0, false)];
+        std::iter::from_fn(move || loop {
+            let (node, depth, expanded) = stack.pop()?;
+            if expanded {
+                return Some((node, depth));
+            }
+            let mut children = node.children().into_iter();
+            let Some(first) = children.next() else {
+                return Some((node, depth));
+            };
+            stack.extend(children.rev().map(|child| (child, depth + 1, false)));
+            stack.push((node, depth, true));
+            stack.push((first, depth + 1, false));
+        })
+    }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Debug for Tree<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.root.display_debug(f, 0)
+        std::fmt::Debug::fmt(&self.root, f)
     }
 }
 
 impl<T: std::fmt::Display> std::fmt::Display for Tree<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.root.display(f, 0)
+        std::fmt::Display::fmt(&self.root, f)
     }
 }
 
@@ -274,45 +330,26 @@ impl<T> Node<T> {
     }
 }
 
-impl<T: std::fmt::Debug> Node<T> {
-    fn display_debug(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
-        for _ in 0..depth {
-            write!(f, "  ")?;
-        }
-        writeln!(f, "{:?}", self.0.inner)?;
-
-        for child in self.children() {
-            child.display_debug(f, depth + 1)?;
-        }
-
-        Ok(())
-    }
-}
-
-impl<T: std::fmt::Display> Node<T> {
-    fn display(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
-        for _ in 0..depth {
-            write!(f, "  ")?;
-        }
-        writeln!(f, "{}", self.0.inner)?;
-
-        for child in self.children() {
-            child.display(f, depth + 1)?;
-        }
-
-        Ok(())
+impl<T> Node<T> {
+    /// Draws the tree below this node like the `tree` command, with `label` writing each value.
+    fn draw(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        label: impl Fn(&mut std::fmt::Formatter<'_>, &T) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        super::draw(f, self.clone(), &|f, node| label(f, node), &Node::children)
     }
 }
 
 impl<T: std::fmt::Debug> std::fmt::Debug for Node<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.display_debug(f, 0)
+        self.draw(f, |f, value| write!(f, "{:?}", value))
     }
 }
 
 impl<T: std::fmt::Display> std::fmt::Display for Node<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.display(f, 0)
+        self.draw(f, |f, value| write!(f, "{}", value))
     }
 }
 
@@ -1,7 +1,34 @@
+//! Disjoint sets: [`UnionFind`] over indices, [`KeyedUnionFind`] over any hashable keys, and
+//! [`RollbackUnionFind`] whose unions can be undone.
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// `UnionFind` keeps track of which of the elements `0..len` are in the same set.
+///
+/// Queries take `&self`: `find` halves the path it walks through a `Cell`, so later lookups stay
+/// fast without needing a mutable borrow.
+///
+/// ```
+/// use aoc::UnionFind;
+///
+/// let mut uf = UnionFind::new(6);
+/// assert!(uf.merge(0, 1));
+/// assert!(uf.merge(1, 2));
+/// uf.union(4, 5);
+/// assert!(!uf.merge(2, 0));
+///
+/// let uf = &uf;
+/// assert!(uf.connected(0, 2));
+/// assert_eq!(uf.size(1), 3);
+/// assert_eq!(uf.num_groups(), 3);
+/// assert_eq!(uf.components(), [vec![0, 1, 2], vec![3], vec![4, 5]]);
+/// assert_eq!(uf.component_sizes(), [3, 2, 1]);
+/// ```
 #[derive(Debug, Clone)]
 pub struct UnionFind {
     sizes: Vec<usize>,
-    ids: Vec<usize>,
+    ids: Vec<Cell<usize>>,
 
     // tracks the number of groups
     num_groups: usize,
@@ -11,17 +38,26 @@ impl UnionFind {
     pub fn new(size: usize) -> Self {
         UnionFind {
             sizes: vec![1; size],
-            ids: (0..size).collect(),
+            ids: (0..size).map(Cell::new).collect(),
             num_groups: size,
         }
     }
 
-    pub fn inner(&self) -> (&[usize], &[usize]) {
-        (&self.sizes, &self.ids)
+    /// `inner` returns the sizes and a copy of the parent of every element.
+    ///
+    /// This used to return `(&[usize], &[usize])`. The parents now live in `Cell`s so that `find`
+    /// can update them through `&self`, and a slice of them cannot be handed out, so every call
+    /// copies them. Use [`components`](Self::components) or
+    /// [`component_sizes`](Self::component_sizes) where possible.
+    pub fn inner(&self) -> (&[usize], Vec<usize>) {
+        (&self.sizes, self.ids.iter().map(Cell::get).collect())
     }
 
     pub fn into_inner(self) -> (Vec<usize>, Vec<usize>) {
-        (self.sizes, self.ids)
+        (
+            self.sizes,
+            self.ids.into_iter().map(Cell::into_inner).collect(),
+        )
     }
 
     /// `len` returns the number of elements in the union find.
@@ -34,62 +70,329 @@ impl UnionFind {
         self.len() == 0
     }
 
-    /// `find` returns the root of the element at index `i`. It also performs
-    /// path compression.
-    pub fn find(&mut self, i: usize) -> usize {
-        let root = self.find_no_compression(i);
-
-        // path compression
-        let mut parent = i;
-        while self.ids[parent] != parent {
-            let tmp = self.ids[parent];
-            self.ids[parent] = root;
-            parent = tmp
+    /// `find` returns the root of the element at index `i`. Every element on the way points to
+    /// its grandparent afterwards (path halving).
+    pub fn find(&self, i: usize) -> usize {
+        let mut node = i;
+        loop {
+            let parent = self.ids[node].get();
+            if parent == node {
+                return node;
+            }
+            let grandparent = self.ids[parent].get();
+            self.ids[node].set(grandparent);
+            node = grandparent;
         }
-
-        root
     }
 
     /// `find_no_compression` returns the root of the element at index `i`
     pub fn find_no_compression(&self, i: usize) -> usize {
         let mut root = i;
-        while self.ids[root] != root {
-            root = self.ids[root]
+        while self.ids[root].get() != root {
+            root = self.ids[root].get()
         }
         root
     }
 
     /// `union` merges the sets containing `i` and `j`.
     pub fn union(&mut self, i: usize, j: usize) {
+        self.merge(i, j);
+    }
+
+    /// `merge` is [`union`](Self::union), but returns false if `i` and `j` already were in the same
+    /// set.
+    pub fn merge(&mut self, i: usize, j: usize) -> bool {
         let root1 = self.find(i);
         let root2 = self.find(j);
 
         if root1 == root2 {
-            return;
+            return false;
         }
 
         // The add the smaller tree to the larger one
         if self.sizes[root1] > self.sizes[root2] {
-            self.ids[root2] = self.ids[root1];
+            self.ids[root2].set(root1);
             self.sizes[root1] += self.sizes[root2];
         } else {
-            self.ids[root1] = self.ids[root2];
+            self.ids[root1].set(root2);
             self.sizes[root2] += self.sizes[root1];
         }
 
         self.num_groups -= 1;
+        true
     }
 
     /// `connected` returns true if the elements at index `i` and `j` are in
     /// the same set.
-    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// `size` returns the size of the set containing the element at index `i`.
+    pub fn size(&self, a: usize) -> usize {
+        self.sizes[self.find(a)]
+    }
+
+    pub fn num_groups(&self) -> usize {
+        self.num_groups
+    }
+
+    /// `components` returns the sets, each in increasing order, ordered by their smallest element.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut index_of_root = HashMap::new();
+        let mut components: Vec<Vec<usize>> = vec![];
+        for i in 0..self.len() {
+            let index = *index_of_root.entry(self.find(i)).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[index].push(i);
+        }
+        components
+    }
+
+    /// `component_sizes` returns the sizes of the sets, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&i| self.ids[i].get() == i)
+            .map(|i| self.sizes[i])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// `KeyedUnionFind` is a [`UnionFind`] over arbitrary keys, such as points or names. Keys get an
+/// index the first time they are seen, and keys that were never added are sets of their own.
+///
+/// ```
+/// use aoc::unionfind::KeyedUnionFind;
+///
+/// let mut uf = KeyedUnionFind::new();
+/// uf.union((162, 817, 812), (425, 690, 689));
+/// uf.union((431, 825, 988), (162, 817, 812));
+/// uf.insert((57, 618, 57));
+///
+/// assert!(uf.connected(&(431, 825, 988), &(425, 690, 689)));
+/// assert!(!uf.connected(&(57, 618, 57), &(162, 817, 812)));
+/// assert_eq!(uf.size(&(162, 817, 812)), 3);
+/// assert_eq!(uf.size(&(0, 0, 0)), 1);
+/// assert_eq!(uf.component_sizes(), [3, 1]);
+/// assert_eq!(uf.components()[1], [&(57, 618, 57)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    keys: Vec<K>,
+    indices: HashMap<K, usize>,
+    sets: UnionFind,
+}
+
+impl<K: Hash + Eq + Clone> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self {
+            keys: vec![],
+            indices: HashMap::new(),
+            sets: UnionFind::new(0),
+        }
+    }
+
+    /// `insert` adds `key` as a set of its own if it is new, and returns its index.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&i) = self.indices.get(&key) {
+            return i;
+        }
+        let i = self.keys.len();
+        self.keys.push(key.clone());
+        self.indices.insert(key, i);
+        self.sets.sizes.push(1);
+        self.sets.ids.push(Cell::new(i));
+        self.sets.num_groups += 1;
+        i
+    }
+
+    /// `index` returns the index of `key`, if it has been added.
+    pub fn index(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    /// `key` returns the key at index `i`.
+    pub fn key(&self, i: usize) -> &K {
+        &self.keys[i]
+    }
+
+    /// `keys` returns all keys, in index order.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// `len` returns the number of keys added so far.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// `is_empty` returns true if no key has been added.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// `find` returns the representative key of the set containing `key`.
+    pub fn find(&self, key: &K) -> Option<&K> {
+        Some(&self.keys[self.sets.find(self.index(key)?)])
+    }
+
+    /// `union` merges the sets containing `a` and `b`, adding them if needed. Returns false if
+    /// they already were the same set.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.merge(a, b)
+    }
+
+    /// `connected` returns true if `a` and `b` are in the same set.
+    pub fn connected(&self, a: &K, b: &K) -> bool {
+        match (self.index(a), self.index(b)) {
+            (Some(a), Some(b)) => self.sets.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// `size` returns the size of the set containing `key`.
+    pub fn size(&self, key: &K) -> usize {
+        self.index(key).map_or(1, |i| self.sets.size(i))
+    }
+
+    /// `num_groups` returns the number of sets among the keys added so far.
+    pub fn num_groups(&self) -> usize {
+        self.sets.num_groups()
+    }
+
+    /// `components` returns the sets, each in the order the keys were added.
+    pub fn components(&self) -> Vec<Vec<&K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| &self.keys[i]).collect())
+            .collect()
+    }
+
+    /// `component_sizes` returns the sizes of the sets, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.sets.component_sizes()
+    }
+
+    /// `as_indices` returns the union find over the indices of the keys.
+    pub fn as_indices(&self) -> &UnionFind {
+        &self.sets
+    }
+}
+
+/// `RollbackUnionFind` is a union find whose unions can be undone, for offline connectivity sweeps
+/// that try a batch of unions and then go back.
+///
+/// It does not compress paths, since that could not be undone, so `find` is O(log n).
+///
+/// ```
+/// use aoc::unionfind::RollbackUnionFind;
+///
+/// let mut uf = RollbackUnionFind::new(4);
+/// uf.union(0, 1);
+/// let before = uf.snapshot();
+/// uf.union(1, 2);
+/// uf.union(2, 3);
+/// assert_eq!(uf.num_groups(), 1);
+///
+/// uf.rollback(before);
+/// assert!(uf.connected(0, 1));
+/// assert!(!uf.connected(1, 2));
+/// assert_eq!(uf.size(3), 1);
+/// assert_eq!(uf.num_groups(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    sizes: Vec<usize>,
+    ids: Vec<usize>,
+    num_groups: usize,
+    /// The root that was attached below another root, for every union that merged two sets
+    history: Vec<usize>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            sizes: vec![1; size],
+            ids: (0..size).collect(),
+            num_groups: size,
+            history: vec![],
+        }
+    }
+
+    /// `len` returns the number of elements in the union find.
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// `is_empty` returns true if the union find contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `find` returns the root of the element at index `i`.
+    pub fn find(&self, i: usize) -> usize {
+        let mut root = i;
+        while self.ids[root] != root {
+            root = self.ids[root];
+        }
+        root
+    }
+
+    /// `union` merges the sets containing `i` and `j`. Returns false if they already were the same
+    /// set, in which case there is nothing to roll back either.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root1, mut root2) = (self.find(i), self.find(j));
+        if root1 == root2 {
+            return false;
+        }
+
+        // Attach the smaller tree below the larger one
+        if self.sizes[root1] > self.sizes[root2] {
+            std::mem::swap(&mut root1, &mut root2);
+        }
+        self.ids[root1] = root2;
+        self.sizes[root2] += self.sizes[root1];
+        self.num_groups -= 1;
+        self.history.push(root1);
+        true
+    }
+
+    /// `snapshot` returns a point to [`rollback`](Self::rollback) to.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `rollback` undoes every union since `snapshot` was taken, newest first.
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            let child = self.history.pop().unwrap();
+            let root = self.ids[child];
+            self.ids[child] = child;
+            self.sizes[root] -= self.sizes[child];
+            self.num_groups += 1;
+        }
+    }
+
+    /// `connected` returns true if the elements at index `i` and `j` are in the same set.
+    pub fn connected(&self, a: usize, b: usize) -> bool {
         self.find(a) == self.find(b)
     }
 
     /// `size` returns the size of the set containing the element at index `i`.
-    pub fn size(&mut self, a: usize) -> usize {
-        let root = self.find(a);
-        self.sizes[root]
+    pub fn size(&self, a: usize) -> usize {
+        self.sizes[self.find(a)]
     }
 
     pub fn num_groups(&self) -> usize {
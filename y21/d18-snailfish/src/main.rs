@@ -1,355 +1,175 @@
-use std::cell::RefCell;
-use std::rc::{Rc, Weak};
-
-use aoc::input_str;
+use aoc::{
+    input_str,
+    tree::{NodeId, Tree},
+};
 use itertools::Itertools;
 
-#[derive(Debug)]
-enum SnailfishValue {
-    Pair {
-        left: Rc<RefCell<SnailfishNumber>>,
-        right: Rc<RefCell<SnailfishNumber>>,
-    },
-    Value(u32),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Element {
+    // A pair has exactly two children, the left and the right element
+    Pair,
+    Regular(u32),
 }
 
-impl SnailfishValue {
-    fn is_pair(&self) -> bool {
-        matches!(self, SnailfishValue::Pair { .. })
-    }
-
-    fn is_value(&self) -> bool {
-        matches!(self, SnailfishValue::Value(_))
-    }
-
-    fn as_pair(&self) -> Option<(&Rc<RefCell<SnailfishNumber>>, &Rc<RefCell<SnailfishNumber>>)> {
-        match self {
-            SnailfishValue::Pair { left, right } => Some((left, right)),
-            _ => None,
-        }
-    }
-
-    fn as_value(&self) -> Option<u32> {
+impl Element {
+    fn as_regular(&self) -> Option<u32> {
         match self {
-            SnailfishValue::Value(val) => Some(*val),
-            _ => None,
+            Element::Regular(n) => Some(*n),
+            Element::Pair => None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct SnailfishNumber {
-    value: SnailfishValue,
-    parent: Weak<RefCell<SnailfishNumber>>,
-    is_left_child: Option<bool>,
+    tree: Tree<Element>,
 }
 
 impl SnailfishNumber {
-    fn new_value(val: u32) -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(SnailfishNumber {
-            value: SnailfishValue::Value(val),
-            parent: Weak::new(),
-            is_left_child: None,
-        }))
-    }
-
-    fn new_pair(left: Rc<RefCell<Self>>, right: Rc<RefCell<Self>>) -> Rc<RefCell<Self>> {
-        let pair = Rc::new(RefCell::new(SnailfishNumber {
-            value: SnailfishValue::Pair {
-                left: left.clone(),
-                right: right.clone(),
+    fn magnitude(&self) -> u32 {
+        self.tree.fold(
+            self.tree.root(),
+            |element, children: &[u32]| match element {
+                Element::Pair => 3 * children[0] + 2 * children[1],
+                Element::Regular(n) => *n,
             },
-            parent: Weak::new(),
-            is_left_child: None,
-        }));
-
-        // Set parent pointers and left/right child flags
-        left.borrow_mut().parent = Rc::downgrade(&pair);
-        left.borrow_mut().is_left_child = Some(true);
-        right.borrow_mut().parent = Rc::downgrade(&pair);
-        right.borrow_mut().is_left_child = Some(false);
-
-        pair
+        )
     }
 
-    fn magnitude(&self) -> u32 {
-        match &self.value {
-            SnailfishValue::Pair { left, right } => {
-                3 * left.borrow().magnitude() + 2 * right.borrow().magnitude()
-            }
-            SnailfishValue::Value(value) => *value,
-        }
+    fn pair(&self, id: NodeId) -> (NodeId, NodeId) {
+        let left = self.tree.first_child(id).unwrap();
+        let right = self.tree.last_child(id).unwrap();
+        (left, right)
     }
 
-    // Find the nearest left neighbor (a Value node to the left of this one)
-    fn find_left_neighbor(&self) -> Option<Rc<RefCell<SnailfishNumber>>> {
-        // First, go up until we find a node where we came from the right
-        let mut current = self.parent.upgrade()?;
-        let mut came_from_left = self.is_left_child?;
-
-        while came_from_left {
-            let parent = current.borrow().parent.upgrade()?;
-            came_from_left = current.borrow().is_left_child?;
-            current = parent;
-        }
-
-        // Now go down to the left subtree and find the rightmost value
-        if let SnailfishValue::Pair { left, .. } = &current.borrow().value {
-            let mut node = left.clone();
+    fn explode(&mut self) -> bool {
+        let root = self.tree.root();
+        let Some(pair) = self
+            .tree
+            .preorder(root)
+            .find(|&id| self.tree[id] == Element::Pair && self.tree.depth(id) == 4)
+        else {
+            return false;
+        };
 
-            // Keep going right until we find a value
-            loop {
-                let is_value = matches!(&node.borrow().value, SnailfishValue::Value(_));
-                if is_value {
-                    return Some(node);
-                }
+        let (left, right) = self.pair(pair);
+        let left_value = self.tree[left].as_regular().unwrap();
+        let right_value = self.tree[right].as_regular().unwrap();
 
-                let next_node = {
-                    let borrowed = node.borrow();
-                    match &borrowed.value {
-                        SnailfishValue::Pair { right, .. } => right.clone(),
-                        _ => unreachable!(),
-                    }
-                };
-                node = next_node;
-            }
+        if let Some(left_neighbor) = self.tree.prev_leaf(pair) {
+            self.tree[left_neighbor] =
+                Element::Regular(self.tree[left_neighbor].as_regular().unwrap() + left_value);
         }
 
-        None
-    }
-
-    // Find the nearest right neighbor (a Value node to the right of this one)
-    fn find_right_neighbor(&self) -> Option<Rc<RefCell<SnailfishNumber>>> {
-        // First, go up until we find a node where we came from the left
-        let mut current = self.parent.upgrade()?;
-        let mut came_from_right = !self.is_left_child?;
-
-        while came_from_right {
-            let parent = current.borrow().parent.upgrade()?;
-            came_from_right = !current.borrow().is_left_child?;
-            current = parent;
+        if let Some(right_neighbor) = self.tree.next_leaf(pair) {
+            self.tree[right_neighbor] =
+                Element::Regular(self.tree[right_neighbor].as_regular().unwrap() + right_value);
         }
 
-        // Now go down to the right subtree and find the leftmost value
-        if let SnailfishValue::Pair { right, .. } = &current.borrow().value {
-            let mut node = right.clone();
-
-            // Keep going left until we find a value
-            loop {
-                let is_value = matches!(&node.borrow().value, SnailfishValue::Value(_));
-                if is_value {
-                    return Some(node);
-                }
-
-                let next_node = {
-                    let borrowed = node.borrow();
-                    match &borrowed.value {
-                        SnailfishValue::Pair { left, .. } => left.clone(),
-                        _ => unreachable!(),
-                    }
-                };
-                node = next_node;
-            }
-        }
+        // The pair is replaced by a regular 0
+        self.tree.clear_children(pair);
+        self.tree[pair] = Element::Regular(0);
 
-        None
+        true
     }
-}
-
-fn explode(node: Rc<RefCell<SnailfishNumber>>, depth: usize) -> bool {
-    if depth == 4 && node.borrow().value.is_pair() {
-        assert!(
-            node.borrow()
-                .value
-                .as_pair()
-                .unwrap()
-                .0
-                .borrow()
-                .value
-                .is_value()
-        );
-        assert!(
-            node.borrow()
-                .value
-                .as_pair()
-                .unwrap()
-                .1
-                .borrow()
-                .value
-                .is_value()
-        );
 
-        let (left, right) = match &node.borrow().value {
-            SnailfishValue::Pair { left, right } => {
-                (left.borrow().magnitude(), right.borrow().magnitude())
-            }
-            _ => unreachable!(),
+    fn split(&mut self) -> bool {
+        let root = self.tree.root();
+        let Some(id) = self
+            .tree
+            .preorder(root)
+            .find(|&id| self.tree[id].as_regular().is_some_and(|n| n >= 10))
+        else {
+            return false;
         };
 
-        if let Some(left_neighbor) = node.borrow().find_left_neighbor() {
-            // assert it's a value
-            assert!(left_neighbor.borrow().value.is_value());
-            let value = left_neighbor.borrow().value.as_value().unwrap() + left;
-            left_neighbor.borrow_mut().value = SnailfishValue::Value(value);
-        }
-
-        if let Some(right_neighbor) = node.borrow().find_right_neighbor() {
-            // assert it's a value
-            assert!(right_neighbor.borrow().value.is_value());
-            let value = right_neighbor.borrow().value.as_value().unwrap() + right;
-            right_neighbor.borrow_mut().value = SnailfishValue::Value(value);
-        }
+        let n = self.tree[id].as_regular().unwrap();
+        self.tree[id] = Element::Pair;
+        self.tree.add_child(id, Element::Regular(n / 2));
+        self.tree.add_child(id, Element::Regular(n.div_ceil(2)));
 
-        // this node needs to be removed
-        node.borrow_mut().value = SnailfishValue::Value(0);
-
-        return true;
+        true
     }
 
-    match &node.borrow().value {
-        SnailfishValue::Pair { left, right } => {
-            explode(left.clone(), depth + 1) || explode(right.clone(), depth + 1)
-        }
-        SnailfishValue::Value(_) => false,
-    }
-}
-
-fn split(node: Rc<RefCell<SnailfishNumber>>) -> bool {
-    if node.borrow().value.is_value() {
-        let n = node.borrow().value.as_value().unwrap();
-        if n >= 10 {
-            let left_val = SnailfishNumber::new_value(n / 2);
-            let right_val = SnailfishNumber::new_value(n.div_ceil(2));
-
-            node.borrow_mut().value = SnailfishValue::Pair {
-                left: left_val.clone(),
-                right: right_val.clone(),
-            };
-
-            // Set up parent pointers for the new children
-            left_val.borrow_mut().parent = Rc::downgrade(&node);
-            left_val.borrow_mut().is_left_child = Some(true);
-            right_val.borrow_mut().parent = Rc::downgrade(&node);
-            right_val.borrow_mut().is_left_child = Some(false);
-
-            true
-        } else {
-            false
+    fn fmt_node(&self, f: &mut std::fmt::Formatter<'_>, id: NodeId) -> std::fmt::Result {
+        match self.tree[id] {
+            Element::Pair => {
+                let (left, right) = self.pair(id);
+                write!(f, "[")?;
+                self.fmt_node(f, left)?;
+                write!(f, ",")?;
+                self.fmt_node(f, right)?;
+                write!(f, "]")
+            }
+            Element::Regular(n) => write!(f, "{}", n),
         }
-    } else {
-        let n = node.borrow();
-        let (left, right) = n.value.as_pair().unwrap();
-        split(left.clone()) || split(right.clone())
     }
 }
 
 impl std::fmt::Display for SnailfishNumber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match &self.value {
-            SnailfishValue::Pair { left, right } => {
-                write!(f, "[{},{}]", left.borrow(), right.borrow())
-            }
-            SnailfishValue::Value(val) => write!(f, "{}", val),
-        }
+        self.fmt_node(f, self.tree.root())
     }
 }
 
-fn add(
-    left: Rc<RefCell<SnailfishNumber>>,
-    right: Rc<RefCell<SnailfishNumber>>,
-) -> Rc<RefCell<SnailfishNumber>> {
-    let result = SnailfishNumber::new_pair(left, right);
-    // println!("start {}", result.borrow());
-    loop {
-        if explode(result.clone(), 0) {
-            // println!("after explode {}", result.borrow());
-            continue;
-        }
-
-        if split(result.clone()) {
-            // println!("after split {}", result.borrow());
-            continue;
-        }
+fn add(left: SnailfishNumber, right: SnailfishNumber) -> SnailfishNumber {
+    let mut tree = Tree::new(Element::Pair);
+    let root = tree.root();
+    tree.append_tree(root, left.tree);
+    tree.append_tree(root, right.tree);
 
-        break;
-    }
+    let mut result = SnailfishNumber { tree };
+    while result.explode() || result.split() {}
     result
 }
 
 // parses a snailfish number
-fn parse(line: &str) -> Rc<RefCell<SnailfishNumber>> {
-    let chars: Vec<char> = line.chars().collect();
-    let mut stack: Vec<Rc<RefCell<SnailfishNumber>>> = Vec::new();
-    let mut i = 0;
+fn parse(line: &str) -> SnailfishNumber {
+    let mut chars = line.chars().peekable();
+    assert_eq!(chars.next(), Some('['), "Expected a pair");
+
+    let mut tree = Tree::new(Element::Pair);
+    let mut current = tree.root();
 
-    while i < chars.len() {
-        match chars[i] {
+    while let Some(c) = chars.next() {
+        match c {
             '[' => {
-                i += 1;
+                current = tree.add_child(current, Element::Pair);
             }
             ']' => {
-                let right = stack.pop().expect("Expected right element");
-                let left = stack.pop().expect("Expected left element");
-                let pair = SnailfishNumber::new_pair(left, right);
-                stack.push(pair);
-                i += 1;
-            }
-            ',' => {
-                i += 1;
+                current = tree.parent(current).unwrap_or(current);
             }
+            ',' => {}
             '0'..='9' => {
-                let mut val = 0u32;
-                while i < chars.len() && chars[i].is_ascii_digit() {
-                    val = val * 10 + chars[i].to_digit(10).unwrap();
-                    i += 1;
+                let mut val = c.to_digit(10).unwrap();
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    val = val * 10 + digit;
+                    chars.next();
                 }
-                stack.push(SnailfishNumber::new_value(val));
+                tree.add_child(current, Element::Regular(val));
             }
             _ => {
-                panic!("Unexpected character: {}", chars[i]);
+                panic!("Unexpected character: {}", c);
             }
         }
     }
 
-    stack.pop().expect("Expected exactly one element remaining")
+    SnailfishNumber { tree }
 }
 
 fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(parse)
-        .reduce(add)
-        .unwrap()
-        .borrow()
-        .magnitude()
+    input.lines().map(parse).reduce(add).unwrap().magnitude()
 }
 
 fn part2(input: &str) -> u32 {
-    let forward = input
-        .lines()
-        .combinations(2)
-        .map(|pair| {
-            let left = parse(pair[0]);
-            let right = parse(pair[1]);
-            let sum = add(left, right);
-            sum.borrow().magnitude()
-        })
-        .max()
-        .unwrap();
-
-    let reverse = input
-        .lines()
-        .combinations(2)
-        .map(|pair| {
-            let left = parse(pair[0]);
-            let right = parse(pair[1]);
-            let sum = add(right, left);
-            sum.borrow().magnitude()
-        })
-        .max()
-        .unwrap();
+    let numbers = input.lines().map(parse).collect_vec();
 
-    forward.max(reverse)
+    numbers
+        .iter()
+        .permutations(2)
+        .map(|pair| add(pair[0].clone(), pair[1].clone()).magnitude())
+        .max()
+        .unwrap()
 }
 
 fn main() {
@@ -386,9 +206,9 @@ mod tests {
 
         for (input, expected) in tests {
             println!("Testing {}", input);
-            let input = parse(input);
-            assert!(explode(input.clone(), 0));
-            assert_eq!(input.borrow().to_string(), expected)
+            let mut input = parse(input);
+            assert!(input.explode());
+            assert_eq!(input.to_string(), expected)
         }
     }
 
@@ -407,9 +227,9 @@ mod tests {
 
         for (input, expected) in tests {
             println!("Testing {}", input);
-            let input = parse(input);
-            assert!(split(input.clone()));
-            assert_eq!(input.borrow().to_string(), expected)
+            let mut input = parse(input);
+            assert!(input.split());
+            assert_eq!(input.to_string(), expected)
         }
     }
 
@@ -419,7 +239,7 @@ mod tests {
         let right = parse("[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]");
         let result = add(left, right);
         assert_eq!(
-            result.borrow().to_string(),
+            result.to_string(),
             "[[[[6,7],[6,7]],[[7,7],[0,7]]],[[[8,7],[7,7]],[[8,8],[8,0]]]]"
         );
     }
@@ -429,10 +249,7 @@ mod tests {
         let left = parse("[[[[4,3],4],4],[7,[[8,4],9]]]");
         let right = parse("[1,1]");
         let result = add(left, right);
-        assert_eq!(
-            result.borrow().to_string(),
-            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
-        );
+        assert_eq!(result.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
     }
 
     #[test]
@@ -470,9 +287,9 @@ mod tests {
             // parse acc and item
             let (left, right) = (parse(acc), parse(item));
             let sum = add(left, right);
-            println!("=\t{}", sum.borrow());
+            println!("=\t{}", sum);
             println!();
-            assert_eq!(sum.borrow().to_string(), sums[i]);
+            assert_eq!(sum.to_string(), sums[i]);
             acc = sums[i];
         }
     }
@@ -481,5 +298,6 @@ mod tests {
     fn test_example() {
         let example = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]\n[[[5,[2,8]],4],[5,[[9,9],0]]]\n[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]\n[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]\n[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]\n[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]\n[[[[5,4],[7,7]],8],[[8,3],8]]\n[[9,3],[[9,9],[6,[4,9]]]]\n[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]\n[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
         assert_eq!(part1(example), 4140);
+        assert_eq!(part2(example), 3993);
     }
 }
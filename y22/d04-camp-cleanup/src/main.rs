@@ -1,3 +1,4 @@
+use aoc::interval::IntervalSet;
 use inpt::Inpt;
 
 #[derive(Debug, Inpt, Clone, Copy)]
@@ -10,22 +11,21 @@ struct Ranges {
 }
 
 impl Ranges {
-    fn part1(&self) -> bool {
-        let r1 = self.start1..=self.end1;
-        let r2 = self.start2..=self.end2;
+    fn sections(&self) -> (IntervalSet<i32>, IntervalSet<i32>) {
+        (
+            IntervalSet::from(self.start1..=self.end1),
+            IntervalSet::from(self.start2..=self.end2),
+        )
+    }
 
-        (r1.contains(&self.start2) && r1.contains(&self.end2))
-            || (r2.contains(&self.start1) && r2.contains(&self.end1))
+    fn part1(&self) -> bool {
+        let (r1, r2) = self.sections();
+        r1.is_subset(&r2) || r2.is_subset(&r1)
     }
 
     fn part2(&self) -> bool {
-        let r1 = self.start1..=self.end1;
-        let r2 = self.start2..=self.end2;
-
-        r1.contains(&self.start2)
-            || r1.contains(&self.end2)
-            || r2.contains(&self.start1)
-            || r2.contains(&self.end1)
+        let (r1, r2) = self.sections();
+        !r1.is_disjoint(&r2)
     }
 }
 
@@ -38,3 +38,17 @@ fn main() {
     let part2 = input.iter().filter(|r| r.part2()).count();
     println!("{}", part2);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+        let input = inpt::inpt::<Vec<Ranges>>(input).unwrap();
+
+        assert_eq!(input.iter().filter(|r| r.part1()).count(), 2);
+        assert_eq!(input.iter().filter(|r| r.part2()).count(), 4);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::VecDeque, time::Instant};
 
-use aoc::*;
+use aoc::{diagram::Diagram, *};
 use inpt::Inpt;
 use itertools::Itertools;
 
@@ -13,15 +13,14 @@ struct Input {
     to: usize,
 }
 
-fn solution(content: &str, stacks: usize) -> (String, String) {
+fn solution(content: &str) -> (String, String) {
     let mut time = Instant::now();
 
-    let mut lines = aoc::get_lines(content).peekable();
+    let mut sections = stringstuff::sections(content);
+    let (drawing, moves) = (sections.next().unwrap(), sections.next().unwrap());
     println!("Read input {:?}", time.elapsed());
     time = Instant::now();
 
-    let mut stacks_part1: Vec<VecDeque<char>> = (0..stacks).map(|_| VecDeque::new()).collect();
-
     //             [C]         [N] [R]
     // [J] [T]     [H]         [P] [L]
     // [F] [S] [T] [B]         [M] [D]
@@ -31,23 +30,18 @@ fn solution(content: &str, stacks: usize) -> (String, String) {
     // [B] [Z] [Z] [T] [V] [S] [V] [S] [D]
     // [W] [P] [P] [D] [G] [P] [B] [P] [V]
     //  1   2   3   4   5   6   7   8   9
-    lines
-        .by_ref()
-        .map_while(|line| if line.is_empty() { None } else { Some(line) })
-        .for_each(|line| {
-            line.chars().enumerate().for_each(|(i, c)| {
-                // we only care about positions 1, 5, 9, etc.
-                if i % 4 == 1 && c != ' ' {
-                    stacks_part1[i / 4].push_front(c);
-                }
-            });
-        });
+    let mut stacks_part1: Vec<VecDeque<char>> = Diagram::new(drawing)
+        .stacks()
+        .into_iter()
+        .map(VecDeque::from)
+        .collect();
 
     // Clone the stacks for part 2
     let mut stacks_part2 = stacks_part1.clone();
 
-    let instructions = lines
-        .map(|line| inpt::inpt::<Input>(&line).unwrap())
+    let instructions = moves
+        .lines()
+        .map(|line| inpt::inpt::<Input>(line).unwrap())
         .collect_vec();
 
     println!("Parse input {:?}", time.elapsed());
@@ -88,7 +82,7 @@ fn solution(content: &str, stacks: usize) -> (String, String) {
 }
 
 fn main() {
-    solution(aoc::input_str!(2022, 5), 9);
+    solution(aoc::input_str!(2022, 5));
 }
 
 #[cfg(test)]
@@ -98,7 +92,7 @@ mod tests {
     #[test]
     fn test_input() {
         assert_eq!(
-            solution(aoc::input_str!(2022, 5), 9),
+            solution(aoc::input_str!(2022, 5)),
             ("LBLVVTVLP".to_string(), "TPFFBDRJD".to_string())
         );
     }
@@ -106,7 +100,7 @@ mod tests {
     #[test]
     fn test_example() {
         assert_eq!(
-            solution(include_str!("../example.txt"), 3),
+            solution(include_str!("../example.txt")),
             ("CMZ".to_string(), "MCD".to_string())
         );
     }
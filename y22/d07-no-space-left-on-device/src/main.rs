@@ -1,115 +1,37 @@
-use std::{cell::RefCell, time::Instant};
+use std::time::Instant;
 
-use aoc::{input_str, Node, Tree};
-use itertools::Itertools;
+use aoc::{input_str, tree::fs::FileSystem};
 
-fn solution(content: &str) -> (usize, usize) {
-    let mut time = Instant::now();
+const DISK_SIZE: u64 = 70000000;
+const NEEDED: u64 = 30000000;
 
-    // Don't count the time it takes to read the input into memory
-    let mut lines = aoc::get_lines(content).peekable();
-    println!("Read input {:?}", time.elapsed());
-    time = Instant::now();
-
-    let file_system: Tree<RefCell<(String, Option<usize>)>> =
-        Tree::new(RefCell::new((String::from("/"), None)));
-    let mut current_node = file_system.root();
-
-    while let Some(line) = lines.next() {
-        let split = line.split_whitespace().collect_vec();
-
-        // Either "$ cd path" or "$ ls"
-        if split[1] == "cd" {
-            let path = split[2];
-
-            if path == ".." {
-                current_node = current_node.parent().unwrap();
-            } else if path == "/" {
-                current_node = file_system.root();
-            } else {
-                current_node = current_node
-                    .children()
-                    .into_iter()
-                    .find(|n| (*n).borrow().0 == path)
-                    .unwrap();
-            }
-        } else if split[1] == "ls" {
-            // next few lines are "dir name" or "size name"
-            while let Some(line) = lines.peek() {
-                let split = line.split_whitespace().collect_vec();
-                // If the next line is a command we're done processing this ls
-                if split[0] == "$" {
-                    break;
-                }
-
-                let name = split[1];
-                if split[0] == "dir" {
-                    current_node.add_child(RefCell::new((name.to_string(), None)));
-                } else {
-                    let size = split[0].parse::<usize>().unwrap();
-                    let mut inner = current_node.borrow_mut();
-
-                    match inner.1 {
-                        Some(ref mut s) => *s += size,
-                        None => inner.1 = Some(size),
-                    }
-                }
-
-                lines.next();
-            }
-        } else {
-            panic!("Unknown command: {}", split[1]);
-        }
-    }
+fn solution(content: &str) -> (u64, u64) {
+    let time = Instant::now();
+    let file_system = FileSystem::from_transcript(content).unwrap_or_else(|e| panic!("{e}"));
     println!("parse: {:?}", time.elapsed());
-    time = Instant::now();
 
-    update_sizes(&mut file_system.root());
-    println!("update_sizes: {:?}", time.elapsed());
-    time = Instant::now();
+    let time = Instant::now();
+    let sizes = file_system.dir_sizes();
 
     // Part 1 - sum the sizes of directories < 100000
-    let sum = file_system
-        .iter_preorder()
-        .filter_map(|n| {
-            let size = n.borrow().1.unwrap();
-            (size < 100000).then_some(size)
-        })
-        .sum::<usize>();
+    let sum = sizes
+        .iter()
+        .map(|&(_, size)| size)
+        .filter(|&size| size < 100000)
+        .sum();
 
     println!("{} {:?}", sum, time.elapsed());
 
-    let max_size = 70000000;
-    let remaining = max_size - file_system.root().borrow().1.unwrap();
-
-    // find the smallest file such that total size - file_system > 30000000
-    let smallest_file_size = file_system
-        .iter_preorder()
-        .map(|n| n.borrow().1.unwrap())
-        .filter(|size| remaining + size > 30000000)
-        .min()
+    // Part 2 - the smallest directory to delete so that there is enough space for the update
+    let used = file_system.total_size(file_system.root());
+    let remaining = DISK_SIZE - used;
+    let (_, smallest_dir_size) = file_system
+        .smallest_dir_freeing(NEEDED.saturating_sub(remaining))
         .unwrap();
 
-    println!("{} {:?}", smallest_file_size, time.elapsed());
-
-    (sum, smallest_file_size)
-}
-
-/// Update the sizes of all the directories to include the sizes of their children
-/// + their own size
-fn update_sizes(node: &mut Node<RefCell<(String, Option<usize>)>>) {
-    let mut size = 0;
+    println!("{} {:?}", smallest_dir_size, time.elapsed());
 
-    for mut child in node.children() {
-        update_sizes(&mut child);
-        size += child.borrow().1.unwrap();
-    }
-
-    let mut inner = node.borrow_mut();
-    match inner.1 {
-        Some(ref mut s) => *s += size,
-        None => inner.1 = Some(size),
-    }
+    (sum, smallest_dir_size)
 }
 
 fn main() {
@@ -1,4 +1,12 @@
-use aoc::{get_mut::GetMany, IterJunk};
+use aoc::{
+    get_mut::GetMany,
+    math,
+    parse::{self, Parser},
+    IterJunk,
+};
+
+/// The input the answers in the tests belong to
+const INPUT: &str = include_str!("../input.txt");
 
 #[derive(Debug, Clone, Copy)]
 enum Op {
@@ -53,72 +61,61 @@ fn run_round(monkeys: &mut [Monkey], worried: Option<i64>) {
     }
 }
 
-// I didn't parse the input, I just hardcoded it because that was easier using
-// chat-gpt
-fn get_monkeys() -> Vec<Monkey> {
-    vec![
-        Monkey {
-            items: vec![89, 84, 88, 78, 70],
-            operation: Op::Mul(5),
-            divisible: 7,
-            send: (6, 7),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![76, 62, 61, 54, 69, 60, 85],
-            operation: Op::Add(1),
-            divisible: 17,
-            send: (0, 6),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![83, 89, 53],
-            operation: Op::Add(8),
-            divisible: 11,
-            send: (5, 3),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![95, 94, 85, 57],
-            operation: Op::Add(4),
-            divisible: 13,
-            send: (0, 1),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![82, 98],
-            operation: Op::Add(7),
-            divisible: 19,
-            send: (5, 2),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![69],
-            operation: Op::Add(2),
-            divisible: 2,
-            send: (1, 3),
-            inspections: 0,
-        },
-        Monkey {
-            items: vec![82, 70, 58, 87, 59, 99, 92, 65],
-            operation: Op::Mul(11),
-            divisible: 5,
-            send: (7, 4),
+impl Monkey {
+    // Monkey 0:
+    //   Starting items: 79, 98
+    //   Operation: new = old * 19
+    //   Test: divisible by 23
+    //     If true: throw to monkey 2
+    //     If false: throw to monkey 3
+    fn parse(p: &mut Parser) -> parse::Result<Self> {
+        p.literal("Monkey ")?;
+        p.uint::<usize>()?;
+        p.literal(":")?;
+        p.newline()?;
+
+        p.literal("  Starting items: ")?;
+        let items = p.separated(", ", |p| p.int())?;
+        p.newline()?;
+
+        p.literal("  Operation: new = old ")?;
+        let operation = if p.try_literal("* old") {
+            Op::Square
+        } else if p.try_literal("* ") {
+            Op::Mul(p.int()?)
+        } else {
+            p.literal("+ ")?;
+            Op::Add(p.int()?)
+        };
+        p.newline()?;
+
+        p.literal("  Test: divisible by ")?;
+        let divisible = p.int()?;
+        p.newline()?;
+
+        p.literal("    If true: throw to monkey ")?;
+        let if_true = p.uint()?;
+        p.newline()?;
+        p.literal("    If false: throw to monkey ")?;
+        let if_false = p.uint()?;
+
+        Ok(Monkey {
+            items,
+            operation,
+            divisible,
+            send: (if_true, if_false),
             inspections: 0,
-        },
-        Monkey {
-            items: vec![91, 53, 96, 98, 68, 82],
-            operation: Op::Square,
-            divisible: 3,
-            send: (4, 2),
-            inspections: 0,
-        },
-    ]
+        })
+    }
+}
+
+fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    parse::parse(input, |p| p.sections(Monkey::parse)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 fn main() {
-    println!("{}", part1(get_monkeys()));
-    println!("{}", part2(get_monkeys()));
+    println!("{}", part1(parse_monkeys(INPUT)));
+    println!("{}", part2(parse_monkeys(INPUT)));
 }
 
 fn part1(mut monkeys: Vec<Monkey>) -> usize {
@@ -136,7 +133,7 @@ fn part1(mut monkeys: Vec<Monkey>) -> usize {
 fn part2(mut monkeys: Vec<Monkey>) -> usize {
     // Optimization: we only care that the numbers are divisible by `monkey.divisible`
     // In order to keep numbers small for part 2, we can simplify numbers by the lcm of all divisors
-    let lcm = monkeys.iter().map(|m| m.divisible).product::<i64>();
+    let lcm = math::lcm_all(monkeys.iter().map(|m| m.divisible));
 
     for _ in 0..10000 {
         run_round(&mut monkeys, Some(lcm));
@@ -151,15 +148,50 @@ fn part2(mut monkeys: Vec<Monkey>) -> usize {
 
 #[cfg(test)]
 mod test {
-    use crate::{get_monkeys, part1, part2};
+    use super::*;
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(parse_monkeys(EXAMPLE)), 10605);
+        assert_eq!(part2(parse_monkeys(EXAMPLE)), 2713310158);
+    }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(get_monkeys()), 55930);
+        assert_eq!(part1(parse_monkeys(INPUT)), 55930);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(get_monkeys()), 14636993466);
+        assert_eq!(part2(parse_monkeys(INPUT)), 14636993466);
     }
 }
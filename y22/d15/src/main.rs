@@ -1,3 +1,89 @@
+use std::collections::HashSet;
+
+use aoc::{
+    geom::manhattan::{find_uncovered, row_coverage, Diamond},
+    input_str, time,
+};
+
+/// (sensor, closest beacon)
+type Report = ((i64, i64), (i64, i64));
+
+// Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+fn parse(input: &str) -> Vec<Report> {
+    input
+        .lines()
+        .map(|line| {
+            let n: Vec<i64> = line
+                .split([',', ':'])
+                .map(|part| part.rsplit('=').next().unwrap().parse().unwrap())
+                .collect();
+            ((n[0], n[1]), (n[2], n[3]))
+        })
+        .collect()
+}
+
+/// Every sensor rules out the diamond reaching up to its closest beacon
+fn diamonds(reports: &[Report]) -> Vec<Diamond> {
+    reports
+        .iter()
+        .map(|&(sensor, beacon)| Diamond::through(sensor, beacon))
+        .collect()
+}
+
+/// Counts the positions in row `y` where there can't be a beacon
+fn part1(reports: &[Report], y: i64) -> i64 {
+    let covered = row_coverage(&diamonds(reports), y);
+
+    let beacons: HashSet<_> = reports
+        .iter()
+        .map(|&(_, beacon)| beacon)
+        .filter(|&(x, by)| by == y && covered.contains(x))
+        .collect();
+
+    covered.total_len() - beacons.len() as i64
+}
+
+/// The distress beacon is the only position within the area that no sensor covers
+fn part2(reports: &[Report], max: i64) -> i64 {
+    let (x, y) = find_uncovered(&diamonds(reports), (0, 0), (max, max)).unwrap();
+    x * 4_000_000 + y
+}
+
 fn main() {
-    println!("Hello, world!");
+    let input = input_str!(2022, 15);
+    let reports = time("Parse", || parse(input));
+
+    let part1 = time("Part 1", || part1(&reports, 2_000_000));
+    println!("Part 1: {}", part1);
+
+    let part2 = time("Part 2", || part2(&reports, 4_000_000));
+    println!("Part 2: {}", part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn test_example() {
+        let reports = parse(EXAMPLE);
+        assert_eq!(reports[0], ((2, 18), (-2, 15)));
+        assert_eq!(part1(&reports, 10), 26);
+        assert_eq!(part2(&reports, 20), 56000011);
+    }
 }
@@ -3,7 +3,7 @@
 
 use std::collections::HashSet;
 
-use aoc::input_str;
+use aoc::{cycle, input_str};
 
 // >>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>
 // blowing pattern (RIGHT, LEFT)
@@ -158,12 +158,78 @@ impl Chamber {
         self.highest_solid
     }
 
-    // part2 requires _a lot_ of rocks (1 trillion)
-    fn part2(&mut self, rocks: usize, blowing_pattern: &[Direction]) -> usize {
-        // Make part 1 basically a lazy iterator
+    /// The shape of the top of the tower, one bitmask per row, highest row first
+    fn skyline(&self) -> [u8; SKYLINE] {
+        let mut rows = [0; SKYLINE];
+        for (i, row) in rows.iter_mut().enumerate() {
+            let Some(y) = self.highest_solid.checked_sub(i) else {
+                break;
+            };
+            for x in 1..=7 {
+                if self.is_solid(x, y) {
+                    *row |= 1 << (x - 1);
+                }
+            }
+        }
+        rows
+    }
+}
+
+// The number of rows at the top of the tower considered when looking for a repeat
+const SKYLINE: usize = 32;
+
+// part2 requires _a lot_ of rocks (1 trillion)
+//
+// The rocks and the jets both repeat, so eventually the top of the tower does too. Once it does the
+// tower grows by the same height every period.
+struct Simulation<'a> {
+    chamber: Chamber,
+    pattern: &'a [Direction],
+    rocks: usize,
+    jet: usize,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(pattern: &'a [Direction]) -> Self {
+        Self {
+            chamber: Chamber::new(),
+            pattern,
+            rocks: 0,
+            jet: 0,
+        }
+    }
+
+    fn step(&mut self) {
+        let Self {
+            chamber,
+            pattern,
+            jet,
+            ..
+        } = self;
+
+        let mut blowing_pattern = std::iter::from_fn(|| {
+            let direction = pattern[*jet];
+            *jet = (*jet + 1) % pattern.len();
+            Some(direction)
+        });
 
-        todo!()
+        chamber.drop_rock(CYCLE[self.rocks % CYCLE.len()], &mut blowing_pattern);
+        self.rocks += 1;
     }
+
+    fn key(&self) -> (usize, usize, [u8; SKYLINE]) {
+        (self.rocks % CYCLE.len(), self.jet, self.chamber.skyline())
+    }
+}
+
+fn part2(rocks: usize, pattern: &[Direction]) -> usize {
+    cycle::extrapolate(
+        Simulation::new(pattern),
+        Simulation::step,
+        Simulation::key,
+        |simulation| simulation.chamber.highest_solid,
+        rocks,
+    )
 }
 
 fn main() {
@@ -175,13 +241,7 @@ fn main() {
         chamber.part1(2022, &mut blowing_pattern.iter().copied().cycle())
     );
 
-    println!(
-        "{}",
-        chamber.part1(
-            1_000_000_000_000,
-            &mut blowing_pattern.iter().copied().cycle()
-        )
-    );
+    println!("{}", part2(1_000_000_000_000, &blowing_pattern));
 }
 
 #[cfg(test)]
@@ -224,6 +284,14 @@ mod tests {
         assert_eq!(chamber.part1(2022, &mut blowing_pattern), 3068);
     }
 
+    #[test]
+    fn test_example_part2() {
+        let pattern = blowing_pattern(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+
+        assert_eq!(part2(2022, &pattern), 3068);
+        assert_eq!(part2(1_000_000_000_000, &pattern), 1514285714288);
+    }
+
     #[test]
     fn test_part1() {
         let pattern = blowing_pattern(input_str!(2022, 17));
@@ -1,21 +1,24 @@
-use std::{collections::HashMap, io::Write};
+use std::io::Write;
 
-use aoc::input_str;
+use aoc::{
+    input_str,
+    intern::{Interner, Symbol},
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 enum Monkey {
-    Add(String, String),
-    Sub(String, String),
-    Mul(String, String),
-    Div(String, String),
+    Add(Symbol, Symbol),
+    Sub(Symbol, Symbol),
+    Mul(Symbol, Symbol),
+    Div(Symbol, Symbol),
     Num(i64),
 
-    Eq(String, String),
+    Eq(Symbol, Symbol),
     Unknown,
 }
 
 impl Monkey {
-    fn into_left_right(self) -> Option<(String, String)> {
+    fn into_left_right(self) -> Option<(Symbol, Symbol)> {
         match self {
             Monkey::Add(left, right) => Some((left, right)),
             Monkey::Sub(left, right) => Some((left, right)),
@@ -25,8 +28,8 @@ impl Monkey {
         }
     }
 
-    fn undo(&self, monkeys: &MonkeyMath, y: &mut i64) -> Option<String> {
-        let (left, right) = match self {
+    fn undo(&self, monkeys: &MonkeyMath, y: &mut i64) -> Option<Symbol> {
+        let (left, right) = match *self {
             Monkey::Add(l, r) => Some((l, r)),
             Monkey::Sub(l, r) => Some((l, r)),
             Monkey::Mul(l, r) => Some((l, r)),
@@ -58,73 +61,79 @@ impl Monkey {
             _ => {}
         }
 
-        Some(eq.clone())
+        Some(eq)
     }
-}
 
-impl std::fmt::Display for Monkey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    // The operator of the expression, for printing
+    fn op(&self) -> Option<&'static str> {
         match self {
-            Monkey::Add(left, right) => write!(f, "({} + {})", left, right),
-            Monkey::Sub(left, right) => write!(f, "({} - {})", left, right),
-            Monkey::Mul(left, right) => write!(f, "({} * {})", left, right),
-            Monkey::Div(left, right) => write!(f, "({} / {})", left, right),
-            Monkey::Num(num) => write!(f, "{}", num),
-            Monkey::Eq(left, right) => write!(f, "{} = {}", left, right),
-            Monkey::Unknown => write!(f, "???"),
+            Monkey::Add(_, _) => Some("+"),
+            Monkey::Sub(_, _) => Some("-"),
+            Monkey::Mul(_, _) => Some("*"),
+            Monkey::Div(_, _) => Some("/"),
+            Monkey::Eq(_, _) => Some("="),
+            _ => None,
         }
     }
 }
 
-struct MonkeyMath {
-    monkeys: HashMap<String, Monkey>,
+// The monkeys are indexed by the symbol of their name
+struct MonkeyMath<'a> {
+    names: Interner<'a>,
+    monkeys: Vec<Option<Monkey>>,
 }
 
-impl MonkeyMath {
-    fn from(input: &str) -> Self {
-        let mut monkeys = HashMap::new();
+impl<'a> MonkeyMath<'a> {
+    fn from(input: &'a str) -> Self {
+        let mut names = Interner::new();
+        let mut monkeys = vec![];
 
         for line in input.lines() {
             let mut parts = line.split(": ");
-            let name = parts.next().unwrap();
+            let name = names.intern(parts.next().unwrap());
             let expr = parts.next().unwrap();
 
             let monkey = if let Ok(num) = expr.parse::<i64>() {
                 Monkey::Num(num)
             } else {
                 let mut parts = expr.split(' ');
-                let left = parts.next().unwrap();
+                let left = names.intern(parts.next().unwrap());
                 let op = parts.next().unwrap();
-                let right = parts.next().unwrap();
+                let right = names.intern(parts.next().unwrap());
 
                 match op {
-                    "+" => Monkey::Add(left.to_string(), right.to_string()),
-                    "-" => Monkey::Sub(left.to_string(), right.to_string()),
-                    "*" => Monkey::Mul(left.to_string(), right.to_string()),
-                    "/" => Monkey::Div(left.to_string(), right.to_string()),
+                    "+" => Monkey::Add(left, right),
+                    "-" => Monkey::Sub(left, right),
+                    "*" => Monkey::Mul(left, right),
+                    "/" => Monkey::Div(left, right),
                     _ => panic!("bad op"),
                 }
             };
 
-            monkeys.insert(name.to_string(), monkey);
+            monkeys.resize(names.len(), None);
+            monkeys[name.index()] = Some(monkey);
         }
 
-        Self { monkeys }
+        Self { names, monkeys }
     }
 
-    fn get(&self, name: &str) -> Option<&Monkey> {
-        self.monkeys.get(name)
+    fn symbol(&self, name: &str) -> Symbol {
+        self.names.get(name).unwrap()
     }
 
-    fn replace(&mut self, name: &str, monkey: Monkey) {
-        self.monkeys.insert(name.to_string(), monkey);
+    fn get(&self, name: Symbol) -> Option<Monkey> {
+        self.monkeys[name.index()]
     }
 
-    fn eval(&self, name: &str) -> Option<i64> {
-        let monkey = self.monkeys.get(name).unwrap();
+    fn replace(&mut self, name: Symbol, monkey: Monkey) -> Option<Monkey> {
+        self.monkeys[name.index()].replace(monkey)
+    }
+
+    fn eval(&self, name: Symbol) -> Option<i64> {
+        let monkey = self.get(name).unwrap();
 
         match monkey {
-            Monkey::Num(num) => Some(*num),
+            Monkey::Num(num) => Some(num),
             Monkey::Add(left, right) => Some(self.eval(left)? + self.eval(right)?),
             Monkey::Sub(left, right) => Some(self.eval(left)? - self.eval(right)?),
             Monkey::Mul(left, right) => Some(self.eval(left)? * self.eval(right)?),
@@ -134,13 +143,13 @@ impl MonkeyMath {
     }
 
     // Reduce the expression as much as possible
-    fn simplify(&mut self, name: &str) -> Option<i64> {
-        let monkey = self.monkeys.get(name).unwrap().clone();
+    fn simplify(&mut self, name: Symbol) -> Option<i64> {
+        let monkey = self.get(name).unwrap();
 
         let value = match monkey {
             Monkey::Add(left, right) => {
-                let left = self.simplify(&left);
-                let right = self.simplify(&right);
+                let left = self.simplify(left);
+                let right = self.simplify(right);
 
                 if let (Some(l), Some(r)) = (left, right) {
                     Some(l + r)
@@ -149,8 +158,8 @@ impl MonkeyMath {
                 }
             }
             Monkey::Sub(left, right) => {
-                let left = self.simplify(&left);
-                let right = self.simplify(&right);
+                let left = self.simplify(left);
+                let right = self.simplify(right);
 
                 if let (Some(l), Some(r)) = (left, right) {
                     Some(l - r)
@@ -159,8 +168,8 @@ impl MonkeyMath {
                 }
             }
             Monkey::Mul(left, right) => {
-                let left = self.simplify(&left);
-                let right = self.simplify(&right);
+                let left = self.simplify(left);
+                let right = self.simplify(right);
 
                 if let (Some(l), Some(r)) = (left, right) {
                     Some(l * r)
@@ -169,8 +178,8 @@ impl MonkeyMath {
                 }
             }
             Monkey::Div(left, right) => {
-                let left = self.simplify(&left);
-                let right = self.simplify(&right);
+                let left = self.simplify(left);
+                let right = self.simplify(right);
 
                 if let (Some(l), Some(r)) = (left, right) {
                     Some(l / r)
@@ -179,8 +188,8 @@ impl MonkeyMath {
                 }
             }
             Monkey::Eq(left, right) => {
-                let _ = self.simplify(&left);
-                let _ = self.simplify(&right);
+                let _ = self.simplify(left);
+                let _ = self.simplify(right);
                 None
             }
             Monkey::Num(num) => Some(num),
@@ -189,53 +198,48 @@ impl MonkeyMath {
 
         if let Some(value) = value {
             if let Some((l, r)) = self
-                .monkeys
-                .insert(name.to_string(), Monkey::Num(value))
+                .replace(name, Monkey::Num(value))
                 .and_then(|m| m.into_left_right())
             {
-                self.monkeys.remove(&l);
-                self.monkeys.remove(&r);
+                self.monkeys[l.index()] = None;
+                self.monkeys[r.index()] = None;
             }
         }
 
         value
     }
 
+    // The monkeys that are left, with their names
+    fn iter(&self) -> impl Iterator<Item = (&'a str, Monkey)> + '_ {
+        self.names
+            .symbols()
+            .filter_map(|name| Some((self.names.resolve(name), self.get(name)?)))
+    }
+
     // Returns the dot notation of the expression, for plotting on graphviz
     fn dot(&self) -> String {
         let mut dot = "digraph {\n".to_string();
 
-        for (name, monkey) in &self.monkeys {
+        for (name, monkey) in self.iter() {
             match monkey {
-                Monkey::Add(left, right) => {
-                    dot.push_str(&format!("\t{} -> {};\n", name, left));
-                    dot.push_str(&format!("\t{} -> {};\n", name, right));
-                    dot.push_str(&format!("\t{} [label=\"+\"];\n", name));
-                }
-                Monkey::Sub(left, right) => {
-                    dot.push_str(&format!("\t{} -> {};\n", name, left));
-                    dot.push_str(&format!("\t{} -> {};\n", name, right));
-                    dot.push_str(&format!("\t{} [label=\"-\"];\n", name));
-                }
-                Monkey::Mul(left, right) => {
-                    dot.push_str(&format!("\t{} -> {};\n", name, left));
-                    dot.push_str(&format!("\t{} -> {};\n", name, right));
-                    dot.push_str(&format!("\t{} [label=\"*\"];\n", name));
-                }
-                Monkey::Div(left, right) => {
-                    dot.push_str(&format!("\t{} -> {};\n", name, left));
-                    dot.push_str(&format!("\t{} -> {};\n", name, right));
-                    dot.push_str(&format!("\t{} [label=\"/\"];\n", name));
-                }
                 Monkey::Num(num) => {
                     dot.push_str(&format!("\t{} [label=\"{}\", shape=box];\n", name, num));
                 }
-                Monkey::Eq(left, right) => {
+                Monkey::Unknown => {}
+                _ => {
+                    let (left, right) = match monkey {
+                        Monkey::Eq(l, r) => (l, r),
+                        _ => monkey.into_left_right().unwrap(),
+                    };
+                    let (left, right) = (self.names.resolve(left), self.names.resolve(right));
                     dot.push_str(&format!("\t{} -> {};\n", name, left));
                     dot.push_str(&format!("\t{} -> {};\n", name, right));
-                    dot.push_str(&format!("\t{} [label=\"=\"];\n", name));
+                    dot.push_str(&format!(
+                        "\t{} [label=\"{}\"];\n",
+                        name,
+                        monkey.op().unwrap()
+                    ));
                 }
-                Monkey::Unknown => {}
             }
         }
 
@@ -247,9 +251,9 @@ impl MonkeyMath {
     // Solves part 2 like an equation
     fn solve(&self) -> i64 {
         let mut y = 0;
-        let mut op = "root".to_string();
+        let mut op = self.symbol("root");
 
-        while let Some(monkey) = self.get(&op) {
+        while let Some(monkey) = self.get(op) {
             if let Some(next) = monkey.undo(self, &mut y) {
                 op = next;
             } else {
@@ -261,7 +265,7 @@ impl MonkeyMath {
     }
 
     // returns true if the expression is Equal and the left and right sides are equal
-    fn equal(&self, name: &str) -> bool {
+    fn equal(&self, name: Symbol) -> bool {
         if let Some(Monkey::Eq(l, r)) = self.get(name) {
             let left = self.eval(l);
             let right = self.eval(r);
@@ -277,10 +281,31 @@ impl MonkeyMath {
     }
 }
 
-impl std::fmt::Display for MonkeyMath {
+impl std::fmt::Display for MonkeyMath<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (name, monkey) in &self.monkeys {
-            writeln!(f, "{}: {}", name, monkey)?;
+        for (name, monkey) in self.iter() {
+            match monkey {
+                Monkey::Num(num) => writeln!(f, "{}: {}", name, num)?,
+                Monkey::Unknown => writeln!(f, "{}: ???", name)?,
+                Monkey::Eq(l, r) => writeln!(
+                    f,
+                    "{}: {} = {}",
+                    name,
+                    self.names.resolve(l),
+                    self.names.resolve(r)
+                )?,
+                _ => {
+                    let (l, r) = monkey.into_left_right().unwrap();
+                    writeln!(
+                        f,
+                        "{}: ({} {} {})",
+                        name,
+                        self.names.resolve(l),
+                        monkey.op().unwrap(),
+                        self.names.resolve(r)
+                    )?
+                }
+            }
         }
         Ok(())
     }
@@ -288,18 +313,18 @@ impl std::fmt::Display for MonkeyMath {
 
 fn part1(input: &str) -> i64 {
     let monkeys = MonkeyMath::from(input);
-    monkeys.eval("root").unwrap()
+    monkeys.eval(monkeys.symbol("root")).unwrap()
 }
 
 fn part2(input: &str) -> i64 {
     let mut monkeys = MonkeyMath::from(input);
-    monkeys.replace("humn", Monkey::Unknown);
+    let (root, humn) = (monkeys.symbol("root"), monkeys.symbol("humn"));
+    monkeys.replace(humn, Monkey::Unknown);
 
-    let root = monkeys.get("root").unwrap().clone();
-    let (left, right) = root.into_left_right().unwrap();
-    monkeys.replace("root", Monkey::Eq(left, right));
+    let (left, right) = monkeys.get(root).unwrap().into_left_right().unwrap();
+    monkeys.replace(root, Monkey::Eq(left, right));
 
-    let _ = monkeys.simplify("root");
+    let _ = monkeys.simplify(root);
 
     // Save the dot notation to a file
     let mut file = std::fs::File::create("graph.dot").unwrap();
@@ -309,8 +334,8 @@ fn part2(input: &str) -> i64 {
 
     // Check +/- 1000 for the right answer
     for i in n - 1000..n + 1000 {
-        monkeys.replace("humn", Monkey::Num(i));
-        if monkeys.equal("root") {
+        monkeys.replace(humn, Monkey::Num(i));
+        if monkeys.equal(root) {
             return i;
         }
     }
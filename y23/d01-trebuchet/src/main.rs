@@ -1,60 +1,24 @@
-use std::collections::HashMap;
+use aoc::{digits::spelled_digits, input_str};
 
-use aoc::input_str;
-
-fn get_lookup_table() -> HashMap<&'static str, &'static str> {
-    let mut numbers = HashMap::new();
-
-    numbers.insert("one", "1one");
-    numbers.insert("two", "2two");
-    numbers.insert("three", "3three");
-    numbers.insert("four", "4four");
-    numbers.insert("five", "5five");
-    numbers.insert("six", "6six");
-    numbers.insert("seven", "7seven");
-    numbers.insert("eight", "8eight");
-    numbers.insert("nine", "9nine");
-
-    numbers
-}
-
-fn get_number(line: impl AsRef<str>) -> usize {
-    println!("{}", line.as_ref());
-
-    let mut numbers = line.as_ref().bytes().filter(u8::is_ascii_digit);
-
-    let first = (numbers.clone().next().unwrap() - b'0') as usize;
-    let last = (numbers.next_back().unwrap() - b'0') as usize;
+/// The first and last digit form the calibration value
+fn get_number(mut digits: impl DoubleEndedIterator<Item = u32>) -> usize {
+    let first = digits.next().unwrap() as usize;
+    let last = digits.next_back().map_or(first, |d| d as usize);
 
     first * 10 + last
 }
 
 fn part1(input: &str) -> usize {
-    input.lines().map(get_number).sum()
-}
-
-fn replace_numbers(numbers: &HashMap<&str, &str>, line: &str) -> String {
-    let mut buf = String::with_capacity(line.len());
-    for c in line.chars() {
-        buf.push(c);
-
-        for (word, digit) in numbers {
-            if buf.ends_with(word) {
-                buf.truncate(buf.len() - word.len());
-                buf.push_str(digit);
-            }
-        }
-    }
-    buf
+    input
+        .lines()
+        .map(|line| get_number(line.chars().filter_map(|c| c.to_digit(10))))
+        .sum()
 }
 
 fn part2(input: &str) -> usize {
-    let lookup_table = get_lookup_table();
-
     input
         .lines()
-        .map(|line| replace_numbers(&lookup_table, line))
-        .map(get_number)
+        .map(|line| get_number(spelled_digits(line)))
         .sum()
 }
 
@@ -81,10 +45,9 @@ mod test {
     }
 
     #[test]
-    fn replace_numbers_test() {
-        let lookup_table = get_lookup_table();
-
+    fn spelled_digits_test() {
         // eighthree
-        assert_eq!(get_number(replace_numbers(&lookup_table, "eightthree")), 83);
+        assert_eq!(get_number(spelled_digits("eightthree")), 83);
+        assert_eq!(get_number(spelled_digits("eighthree")), 83);
     }
 }
@@ -1,151 +1,30 @@
 use rayon::prelude::*;
 use std::ops::Range;
 
-use aoc::input_str;
-
-#[derive(Debug, Clone)]
-struct MapEntry {
-    input: Range<u64>,
-    output: Range<u64>,
-}
-
-impl MapEntry {
-    fn new(output_start: u64, input_start: u64, length: u64) -> Self {
-        Self {
-            input: input_start..input_start + length,
-            output: output_start..output_start + length,
-        }
-    }
-
-    fn contains(&self, value: u64) -> bool {
-        self.input.contains(&value)
-    }
-
-    fn process(&self, value: u64) -> Option<u64> {
-        if self.contains(value) {
-            Some(value - self.input.start + self.output.start)
-        } else {
-            None
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Map {
-    entries: Vec<MapEntry>,
-}
-
-impl Map {
-    fn new() -> Self {
-        Self { entries: vec![] }
-    }
-
-    // Maps a single value through the map
-    fn process(&self, value: u64) -> u64 {
-        self.entries
-            .iter()
-            .find_map(|entry| entry.process(value))
-            .unwrap_or(value)
-    }
-
-    // Maps a range of values through the map
-    //
-    // - The output range might not be continuous
-    // - The input range is way too large to iterate over
-    // - Parts of the range are going to be mapped to the same value
-    fn process_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
-        let mut ranges = vec![];
-
-        let mut start = range.start;
-        while start != range.end {
-            // Find the first entry that contains the start value
-            let entry = self.entries.iter().find(|entry| entry.contains(start));
-
-            match entry {
-                Some(entry) => {
-                    // Find the end of the range
-                    let end = entry.input.end.min(range.end);
-
-                    // Map the range
-                    let output_start = entry.output.start + (start - entry.input.start);
-                    let output_end = entry.output.start + (end - entry.input.start);
-
-                    // Add the range to the list
-                    ranges.push(output_start..output_end);
-
-                    // Move the start to the end of the range
-                    start = end;
-                }
-                None => {
-                    // No entry contains the start value, so we need to find the next closest entry
-                    let next_entry = self
-                        .entries
-                        .iter()
-                        .filter(|entry| entry.input.start > start)
-                        .min_by_key(|entry| entry.input.start);
-
-                    match next_entry {
-                        Some(next) => {
-                            // Map until the next entry
-                            let end = next.input.start.min(range.end);
-                            ranges.push(start..end);
-                            start = end;
-                        }
-                        None => {
-                            // No more entries, so map until the end of the range
-                            ranges.push(start..range.end);
-                            start = range.end;
-                        }
-                    }
-                }
-            }
-        }
-
-        ranges
-    }
-}
-
-impl Extend<MapEntry> for Map {
-    fn extend<T: IntoIterator<Item = MapEntry>>(&mut self, iter: T) {
-        self.entries.extend(iter);
-    }
-}
-
-impl FromIterator<MapEntry> for Map {
-    fn from_iter<T: IntoIterator<Item = MapEntry>>(iter: T) -> Self {
-        let mut map = Self::new();
-        map.extend(iter);
-        map
-    }
-}
+use aoc::{
+    input_str,
+    interval::{IntervalSet, RangeMap},
+    stringstuff::{ints_n, sections, uints},
+};
 
 #[derive(Debug, Clone)]
 struct Almanac {
     seeds: Vec<u64>,
-    maps: Vec<Map>,
+    maps: Vec<RangeMap<u64>>,
 }
 
 impl Almanac {
     // Runs the seed through all the maps
     fn part1(&self, seed: u64) -> u64 {
-        let mut value = seed;
-        for map in &self.maps {
-            value = map.process(value);
-        }
-        value
+        self.maps.iter().fold(seed, |value, map| map.map(value))
     }
 
     // Processes ranges through all the maps
-    fn part2(&self, range: Range<u64>) -> Vec<Range<u64>> {
-        let mut ranges = vec![range];
-        for map in &self.maps {
-            ranges = ranges
-                .into_iter()
-                .flat_map(|range| map.process_range(range))
-                .collect();
-        }
-
-        ranges
+    //
+    // - The input ranges are way too large to iterate over
+    // - Every map splits them up into smaller ranges
+    fn part2(&self, seeds: IntervalSet<u64>) -> IntervalSet<u64> {
+        self.maps.iter().fold(seeds, |set, map| map.map_set(&set))
     }
 
     fn part2_brute(&self, range: Range<u64>) -> u64 {
@@ -157,39 +36,19 @@ impl Almanac {
     }
 }
 
-fn parse_map<'a>(mut lines: impl Iterator<Item = &'a str>) -> Map {
-    lines
-        .by_ref()
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .map(|parts| MapEntry::new(parts[0], parts[1], parts[2]))
-        .collect::<Map>()
+fn parse_map(section: &str) -> RangeMap<u64> {
+    section
+        .lines()
+        .skip(1)
+        .map(ints_n::<u64, 3>)
+        .map(|[destination, source, len]| (source..source + len, destination))
+        .collect()
 }
 
 fn parse_almanac(input: &str) -> Almanac {
-    let mut lines = input.lines();
-    let seeds = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|seed| seed.parse().unwrap())
-        .collect::<Vec<_>>();
-
-    lines.next();
-
-    let mut maps = vec![];
-    loop {
-        if lines.next().is_none() {
-            break;
-        }
-
-        maps.push(parse_map(&mut lines));
-    }
+    let mut sections = sections(input);
+    let seeds = uints(sections.next().unwrap()).collect();
+    let maps = sections.map(parse_map).collect();
 
     Almanac { seeds, maps }
 }
@@ -211,11 +70,8 @@ fn part2(almanac: &Almanac) -> u64 {
         .chunks(2)
         .map(|chunk| chunk[0]..(chunk[0] + chunk[1]));
 
-    // Process the ranges through all the maps
-    let ranges = ranges.flat_map(|range| almanac.part2(range));
-
-    // Find the minimum value in the ranges
-    ranges.map(|range| range.start).min().unwrap()
+    // Process the ranges through all the maps and find the minimum location
+    almanac.part2(ranges.collect()).min().unwrap()
 }
 
 fn part2_brute(almanac: &Almanac) -> u64 {
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, ops::Add, process::exit, thread};
 
-use aoc::input_str;
+use aoc::{input_str, math::lcm};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Node {
@@ -173,7 +173,7 @@ fn part2(directions: &str, map: &HashMap<[u8; 3], Node>) -> usize {
     map.keys()
         .filter(|&name| name[2] == b'A')
         .map(|&start| run(directions, map, start, |name| name[2] == b'Z'))
-        .fold(1, num::integer::lcm)
+        .fold(1, lcm)
 }
 
 fn part1_new(directions: &str, map: &HashMap<[u8; 3], Node>) {
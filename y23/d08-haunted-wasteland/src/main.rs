@@ -1,90 +1,79 @@
-use std::{collections::HashMap, ops::Add};
-
-use aoc::input_str;
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Node {
-    name: [u8; 3],
-    left: [u8; 3],
-    right: [u8; 3],
+use std::ops::Add;
+
+use aoc::{
+    input_str,
+    intern::{parse_adjacency, Interner},
+    math::lcm,
+    stringstuff::sections,
+};
+
+/// The left and right node of every node, by index.
+struct Network<'a> {
+    next: Vec<[usize; 2]>,
+    names: Interner<'a>,
 }
 
-impl Node {
-    fn new(name: [u8; 3], left: [u8; 3], right: [u8; 3]) -> Self {
-        Self { name, left, right }
-    }
-
-    fn name(&self) -> [u8; 3] {
-        self.name
-    }
-
-    fn next(&self, direction: char) -> [u8; 3] {
+impl Network<'_> {
+    fn next(&self, node: usize, direction: char) -> usize {
         match direction {
-            'L' => self.left,
-            'R' => self.right,
+            'L' => self.next[node][0],
+            'R' => self.next[node][1],
             _ => unreachable!(),
         }
     }
-}
-
-fn parse(input: &str) -> (String, HashMap<[u8; 3], Node>) {
-    let mut lines = input.lines();
-
-    let directions = lines.next().unwrap().to_owned();
-
-    let mut map = HashMap::new();
-    for line in lines.skip(1) {
-        let bytes = line.as_bytes();
-
-        let name = [bytes[0], bytes[1], bytes[2]];
-        let left = [bytes[7], bytes[8], bytes[9]];
-        let right = [bytes[12], bytes[13], bytes[14]];
 
-        map.insert(name, Node::new(name, left, right));
+    fn name(&self, node: usize) -> &str {
+        self.names.names()[node]
     }
+}
+
+fn parse(input: &str) -> (&str, Network<'_>) {
+    let mut sections = sections(input);
+    let directions = sections.next().unwrap();
+    let (adj, names) = parse_adjacency(sections.next().unwrap(), " = ");
+    let next = adj.into_iter().map(|lr| [lr[0], lr[1]]).collect();
 
-    (directions, map)
+    (directions.trim(), Network { next, names })
 }
 
-fn run(
-    directions: &str,
-    map: &HashMap<[u8; 3], Node>,
-    start: [u8; 3],
-    is_end: impl Fn(&[u8; 3]) -> bool,
-) -> usize {
+fn run(directions: &str, network: &Network, start: usize, is_end: impl Fn(&str) -> bool) -> usize {
     directions
         .chars()
         .cycle()
-        .scan(map.get(&start).unwrap(), |current, direction| {
-            *current = map.get(&current.next(direction)).unwrap();
-            Some(current.name())
+        .scan(start, |current, direction| {
+            *current = network.next(*current, direction);
+            Some(*current)
         })
-        .take_while(|node| !is_end(node))
+        .take_while(|&node| !is_end(network.name(node)))
         .count()
         .add(1)
 }
 
-fn part1(directions: &str, map: &HashMap<[u8; 3], Node>) -> usize {
-    run(directions, map, *b"AAA", |n| n == b"ZZZ")
+fn part1(directions: &str, network: &Network) -> usize {
+    let start = network.names.get("AAA").unwrap().index();
+    run(directions, network, start, |name| name == "ZZZ")
 }
 
-fn part2(directions: &str, map: &HashMap<[u8; 3], Node>) -> usize {
-    map.keys()
-        .filter(|&name| name[2] == b'A')
-        .map(|&start| run(directions, map, start, |name| name[2] == b'Z'))
-        .fold(1, num::integer::lcm)
+fn part2(directions: &str, network: &Network) -> usize {
+    network
+        .names
+        .symbols()
+        .map(|symbol| symbol.index())
+        .filter(|&node| network.name(node).ends_with('A'))
+        .map(|start| run(directions, network, start, |name| name.ends_with('Z')))
+        .fold(1, lcm)
 }
 
 fn main() {
     let input = input_str!(2023, 8);
-    let (directions, map) = parse(input);
+    let (directions, network) = parse(input);
 
     let time = std::time::Instant::now();
-    println!("Part 1: {}", part1(&directions, &map));
+    println!("Part 1: {}", part1(directions, &network));
     println!("Time: {:?}", time.elapsed());
 
     let time = std::time::Instant::now();
-    println!("Part 2: {}", part2(&directions, &map));
+    println!("Part 2: {}", part2(directions, &network));
     println!("Time: {:?}", time.elapsed());
 }
 
@@ -92,20 +81,36 @@ fn main() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_example_part1() {
+        let input = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        let (directions, network) = parse(input);
+
+        assert_eq!(part1(directions, &network), 6);
+    }
+
+    #[test]
+    fn test_example_crlf() {
+        let input = "LLR\r\n\r\nAAA = (BBB, BBB)\r\nBBB = (AAA, ZZZ)\r\nZZZ = (ZZZ, ZZZ)\r\n";
+        let (directions, network) = parse(input);
+
+        assert_eq!(part1(directions, &network), 6);
+    }
+
     #[test]
     fn test_example_part2() {
         let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)";
-        let (directions, map) = parse(input);
+        let (directions, network) = parse(input);
 
-        assert_eq!(part2(&directions, &map), 6);
+        assert_eq!(part2(directions, &network), 6);
     }
 
     #[test]
     fn verify() {
         let input = input_str!(2023, 8);
-        let (directions, map) = parse(input);
+        let (directions, network) = parse(input);
 
-        assert_eq!(part1(&directions, &map), 20659);
-        assert_eq!(part2(&directions, &map), 15690466351717);
+        assert_eq!(part1(directions, &network), 20659);
+        assert_eq!(part2(directions, &network), 15690466351717);
     }
 }
@@ -1,18 +1,10 @@
-use aoc::input_str;
-
-fn diff(sequence: &[i32]) -> Vec<i32> {
-    sequence.windows(2).map(|w| w[1] - w[0]).collect()
-}
+use aoc::{input_str, math::poly};
 
+/// The extrapolated values before the start and after the end of the sequence.
 fn zipped(sequence: &[i32]) -> (i32, i32) {
-    if sequence.iter().copied().all(|n| n == 0) {
-        return (0, 0);
-    }
-
-    let (first, last) = zipped(&diff(sequence));
     (
-        sequence.first().unwrap() - first,
-        sequence.last().unwrap() + last,
+        poly::extrapolate_backward(sequence),
+        poly::extrapolate_forward(sequence),
     )
 }
 
@@ -9,7 +9,11 @@
 
 use std::collections::{HashMap, HashSet};
 
-use aoc::input_str;
+use aoc::{
+    error::{self, parse_grid},
+    geom::polygon,
+    input_str,
+};
 use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -25,17 +29,17 @@ enum Cell {
 }
 
 impl Cell {
-    fn from_char(c: char) -> Cell {
+    fn from_char(c: char) -> Option<Cell> {
         match c {
-            '|' => Cell::Vertical,
-            '-' => Cell::Horizontal,
-            'L' => Cell::NorthEast,
-            'J' => Cell::NorthWest,
-            '7' => Cell::SouthWest,
-            'F' => Cell::SouthEast,
-            '.' => Cell::Ground,
-            'S' => Cell::Start,
-            _ => panic!("Invalid cell"),
+            '|' => Some(Cell::Vertical),
+            '-' => Some(Cell::Horizontal),
+            'L' => Some(Cell::NorthEast),
+            'J' => Some(Cell::NorthWest),
+            '7' => Some(Cell::SouthWest),
+            'F' => Some(Cell::SouthEast),
+            '.' => Some(Cell::Ground),
+            'S' => Some(Cell::Start),
+            _ => None,
         }
     }
 }
@@ -114,7 +118,8 @@ impl Graph {
             false
         }
 
-        for &node in self.nodes.keys() {
+        // Start from S so the loop through it is found first
+        for &node in std::iter::once(&self.start).chain(self.nodes.keys()) {
             if !visited.contains(&node)
                 && dfs_cycle(node, None, self, &mut visited, &mut parent, &mut cycle)
             {
@@ -168,52 +173,40 @@ impl std::fmt::Display for Graph {
     }
 }
 
-fn parse_graph(input: &str) -> Graph {
+fn parse_graph(input: &str) -> error::Result<Graph> {
+    let rows = parse_grid(input, Cell::from_char, "a pipe, '.' or 'S'")?;
+
     let mut graph = Graph::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            graph.add_node(x, y, Cell::from_char(c));
+    for (y, row) in rows.into_iter().enumerate() {
+        for (x, cell) in row.into_iter().enumerate() {
+            graph.add_node(x, y, cell);
         }
     }
-    graph
-}
-
-fn shoelace_formula(points: &[(i64, i64)]) -> i64 {
-    let n = points.len();
-    let mut sum = 0;
-
-    for i in 0..n {
-        let j = (i + 1) % n;
-        sum += points[i].0 * points[j].1 - points[j].0 * points[i].1;
-    }
-
-    sum = sum.abs();
-    sum / 2
+    Ok(graph)
 }
 
 fn main() {
-    let input = input_str!(2023, 10);
-    let graph = parse_graph(input);
-
-    println!("{}", graph);
+    error::run(|| {
+        let input = input_str!(2023, 10);
+        let graph = parse_graph(input)?;
 
-    // Run the cycle finder
-    let cycle = graph.find_cycle();
+        println!("{}", graph);
 
-    println!("{}", cycle.len() / 2);
+        // Run the cycle finder
+        let cycle = graph.find_cycle();
 
-    // Find the area of the cycle
-    let points: Vec<(i64, i64)> = cycle
-        .iter()
-        .cloned()
-        .map(|(x, y)| (x as i64, y as i64))
-        .collect();
+        println!("{}", cycle.len() / 2);
 
-    let area = shoelace_formula(&points);
-    let length = cycle.len() as i64 / 2;
-    let result = area - length + 1;
+        // The tiles enclosed by the loop are the lattice points strictly inside it
+        let points: Vec<(i64, i64)> = cycle
+            .iter()
+            .cloned()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect();
 
-    println!("{}", result);
+        println!("{}", polygon::interior_points(&points));
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -223,22 +216,17 @@ mod tests {
     #[test]
     fn test_example_2() {
         let input = ".F----7F7F7F7F-7....\n.|F--7||||||||FJ....\n.||.FJ||||||||L7....\nFJL7L7LJLJ||LJ.L-7..\nL--J.L7...LJS7F-7L7.\n....F-J..F7FJ|L7L7L7\n....L7.F7||L7|.L7L7|\n.....|FJLJ|FJ|F7|.LJ\n....FJL-7.||.||||...\n....L---J.LJ.LJLJ...";
-        let graph = parse_graph(input);
+        let graph = parse_graph(input).unwrap();
         let cycle = graph.find_cycle();
 
         println!("{}", cycle.len() / 2);
 
-        // shoelace formula
         let points: Vec<(i64, i64)> = cycle
             .iter()
             .cloned()
             .map(|(x, y)| (x as i64, y as i64))
             .collect();
 
-        let area = shoelace_formula(&points);
-
-        println!("{}", area);
-
-        println!("{}", area - (cycle.len() / 2) as i64 + 1);
+        assert_eq!(polygon::interior_points(&points), 8);
     }
 }
@@ -1,8 +1,8 @@
-use std::hash::{DefaultHasher, Hasher};
-
-use aoc::input_str;
-use itertools::Itertools;
-use nohash_hasher::IntMap;
+use aoc::{
+    cycle,
+    error::{self, parse_grid},
+    input_str,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
@@ -13,12 +13,12 @@ enum Space {
 }
 
 impl Space {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '.' => Self::Empty,
-            '#' => Self::Square,
-            'O' => Self::Round,
-            _ => panic!("Invalid space"),
+            '.' => Some(Self::Empty),
+            '#' => Some(Self::Square),
+            'O' => Some(Self::Round),
+            _ => None,
         }
     }
 
@@ -39,7 +39,7 @@ impl Space {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Reflector {
     spaces: Vec<Space>,
     width: usize,
@@ -47,23 +47,14 @@ struct Reflector {
 }
 
 impl Reflector {
-    fn from_str(input: &str) -> Self {
-        let mut height = 0;
-        let spaces = input
-            .lines()
-            .map(|s| s.chars())
-            .flat_map(|line| {
-                height += 1;
-                line
-            })
-            .map(Space::from_char)
-            .collect_vec();
-
-        Self {
-            width: spaces.len() / height,
-            spaces,
-            height,
-        }
+    fn from_str(input: &str) -> error::Result<Self> {
+        let rows = parse_grid(input, Space::from_char, "'.', '#' or 'O'")?;
+
+        Ok(Self {
+            width: rows.first().map_or(0, Vec::len),
+            height: rows.len(),
+            spaces: rows.concat(),
+        })
     }
 
     fn get(&self, x: usize, y: usize) -> Space {
@@ -188,17 +179,6 @@ impl Reflector {
             .map(|(f, row)| f * row.iter().filter(|space| space.is_round()).count())
             .sum()
     }
-
-    fn hash(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
-
-        // SAFETY: `Space` is repr(u8), which should mean this is fine
-        let bytes = unsafe {
-            std::slice::from_raw_parts(self.spaces.as_ptr() as *const u8, self.spaces.len())
-        };
-        hasher.write(bytes);
-        hasher.finish()
-    }
 }
 
 impl std::fmt::Display for Reflector {
@@ -216,41 +196,27 @@ fn part1(mut reflector: Reflector) -> usize {
     reflector.north_load()
 }
 
-fn part2(mut reflector: Reflector) -> usize {
-    let goal = 1_000_000_000;
-
-    let mut seen = IntMap::default();
-    seen.insert(reflector.hash(), 0);
-
-    for i in 1.. {
-        reflector.cycle();
-        if let Some(&j) = seen.get(&reflector.hash()) {
-            let remaining = (goal - i) % (i - j);
-            for _ in 0..remaining {
-                reflector.cycle();
-            }
-            break;
-        }
-        seen.insert(reflector.hash(), i);
-    }
-
-    reflector.north_load()
+fn part2(reflector: Reflector) -> usize {
+    cycle::nth(reflector, Reflector::cycle, 1_000_000_000).north_load()
 }
 
 fn main() {
-    let input = input_str!(2023, 14);
+    error::run(|| {
+        let input = input_str!(2023, 14);
 
-    let start = std::time::Instant::now();
-    let reflector = Reflector::from_str(input);
-    println!("Parse: {:?}", start.elapsed());
+        let start = std::time::Instant::now();
+        let reflector = Reflector::from_str(input)?;
+        println!("Parse: {:?}", start.elapsed());
 
-    let start = std::time::Instant::now();
-    println!("Part 1: {}", part1(reflector.clone()));
-    println!("Time: {:?}", start.elapsed());
+        let start = std::time::Instant::now();
+        println!("Part 1: {}", part1(reflector.clone()));
+        println!("Time: {:?}", start.elapsed());
 
-    let start = std::time::Instant::now();
-    println!("Part 2: {}", part2(reflector));
-    println!("Time: {:?}", start.elapsed());
+        let start = std::time::Instant::now();
+        println!("Part 2: {}", part2(reflector));
+        println!("Time: {:?}", start.elapsed());
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -261,7 +227,7 @@ mod tests {
     fn test_example() {
         let input = "O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....";
 
-        let reflector = Reflector::from_str(input);
+        let reflector = Reflector::from_str(input).unwrap();
         println!("{}", reflector);
 
         let r1 = reflector.clone();
@@ -270,4 +236,11 @@ mod tests {
         let r2 = reflector.clone();
         assert_eq!(part2(r2), 64);
     }
+
+    #[test]
+    fn test_invalid_space() {
+        let error = Reflector::from_str("O....\nO.O?#\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.snippet, "O.O?#");
+    }
 }
@@ -1,3 +1,85 @@
+use aoc::{geom::polygon, input_str, time};
+
+/// Dig `len` meters in direction `(dx, dy)`
+type Step = ((i64, i64), i64);
+
+// R 6 (#70c710)
+fn parse_line(line: &str) -> (Step, Step) {
+    let mut parts = line.split_whitespace();
+    let dir = parts.next().unwrap();
+    let len = parts.next().unwrap().parse().unwrap();
+    let color = parts.next().unwrap().trim_matches(['(', ')', '#']);
+
+    // The real instruction is hidden in the color: five hex digits of length, then the direction
+    let (hex_len, hex_dir) = color.split_at(5);
+    let big_len = i64::from_str_radix(hex_len, 16).unwrap();
+    let big_dir = match hex_dir {
+        "0" => "R",
+        "1" => "D",
+        "2" => "L",
+        "3" => "U",
+        _ => panic!("Invalid direction {hex_dir}"),
+    };
+
+    ((direction(dir), len), (direction(big_dir), big_len))
+}
+
+fn direction(dir: &str) -> (i64, i64) {
+    match dir {
+        "R" => (1, 0),
+        "D" => (0, 1),
+        "L" => (-1, 0),
+        "U" => (0, -1),
+        _ => panic!("Invalid direction {dir}"),
+    }
+}
+
+/// The lagoon covers every cube on the trench and inside it
+fn lagoon_size(plan: impl IntoIterator<Item = Step>) -> i64 {
+    let trench = polygon::from_walk((0, 0), plan);
+    polygon::lattice_points(&trench)
+}
+
+fn part1(input: &str) -> i64 {
+    lagoon_size(input.lines().map(|line| parse_line(line).0))
+}
+
+fn part2(input: &str) -> i64 {
+    lagoon_size(input.lines().map(|line| parse_line(line).1))
+}
+
 fn main() {
-    println!("Hello, world!");
+    let input = input_str!(2023, 18);
+
+    let part1 = time("Part 1", || part1(input));
+    println!("Part 1: {}", part1);
+
+    let part2 = time("Part 2", || part2(input));
+    println!("Part 2: {}", part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(EXAMPLE), 62);
+        assert_eq!(part2(EXAMPLE), 952408144115);
+    }
 }
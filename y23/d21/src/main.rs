@@ -1,3 +1,124 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc::{input_str, math::poly, time};
+
+struct Garden {
+    rocks: Vec<Vec<bool>>,
+    start: (i64, i64),
+}
+
+impl Garden {
+    fn parse(input: &str) -> Self {
+        let mut start = (0, 0);
+        let rocks = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.bytes()
+                    .enumerate()
+                    .map(|(x, b)| {
+                        if b == b'S' {
+                            start = (x as i64, y as i64);
+                        }
+                        b == b'#'
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { rocks, start }
+    }
+
+    fn size(&self) -> i64 {
+        self.rocks.len() as i64
+    }
+
+    /// The map repeats infinitely in every direction
+    fn is_rock(&self, (x, y): (i64, i64)) -> bool {
+        let row = &self.rocks[y.rem_euclid(self.size()) as usize];
+        row[x.rem_euclid(row.len() as i64) as usize]
+    }
+
+    /// The number of plots reachable in exactly `steps` steps, for each of `steps`.
+    ///
+    /// Stepping back and forth wastes two steps, so a plot is reachable in exactly `n` steps if its
+    /// distance is at most `n` and has the same parity.
+    fn reachable(&self, steps: &[i64]) -> Vec<i64> {
+        let max = steps.iter().copied().max().unwrap_or(0);
+        let mut distances = HashMap::from([(self.start, 0)]);
+        let mut queue = VecDeque::from([self.start]);
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[&(x, y)];
+            if distance == max {
+                continue;
+            }
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if !self.is_rock(next) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        steps
+            .iter()
+            .map(|&n| {
+                distances
+                    .values()
+                    .filter(|&&d| d <= n && d % 2 == n % 2)
+                    .count() as i64
+            })
+            .collect()
+    }
+}
+
+fn part1(input: &str) -> i64 {
+    Garden::parse(input).reachable(&[64])[0]
+}
+
+/// The start is in the middle of an empty row and column, so the reachable area grows like a
+/// diamond and every time it crosses another map the count follows the same quadratic.
+fn part2(input: &str) -> i64 {
+    const STEPS: i64 = 26501365;
+
+    let garden = Garden::parse(input);
+    let size = garden.size();
+    let samples: Vec<_> = (0..3).map(|k| STEPS % size + k * size).collect();
+    let counts = garden.reachable(&samples);
+    poly::extrapolate(&counts, STEPS / size)
+}
+
 fn main() {
-    println!("Hello, world!");
+    let input = input_str!(2023, 21);
+
+    let part1 = time("Part 1", || part1(input));
+    println!("Part 1: {}", part1);
+
+    let part2 = time("Part 2", || part2(input));
+    println!("Part 2: {}", part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_example() {
+        let garden = Garden::parse(EXAMPLE);
+        assert_eq!(
+            garden.reachable(&[6, 10, 50, 100, 500]),
+            [16, 50, 1594, 6536, 167004]
+        );
+    }
 }
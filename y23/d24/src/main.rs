@@ -1,3 +1,62 @@
+use aoc::{
+    geom::line::{crosses_within, Ray},
+    input_str, time,
+};
+
+// 19, 13, 30 @ -2,  1, -2
+fn parse(input: &str) -> Vec<([i128; 3], [i128; 3])> {
+    let triple = |s: &str| -> [i128; 3] {
+        let v: Vec<i128> = s.split(',').map(|n| n.trim().parse().unwrap()).collect();
+        [v[0], v[1], v[2]]
+    };
+
+    input
+        .lines()
+        .map(|line| line.split_once('@').unwrap())
+        .map(|(position, velocity)| (triple(position), triple(velocity)))
+        .collect()
+}
+
+/// Counts the pairs of hailstones whose paths cross inside the test area, ignoring the z axis
+fn part1(hailstones: &[([i128; 3], [i128; 3])], min: i128, max: i128) -> usize {
+    let rays: Vec<Ray> = hailstones
+        .iter()
+        .map(|([x, y, _], [vx, vy, _])| Ray {
+            origin: (*x, *y),
+            velocity: (*vx, *vy),
+        })
+        .collect();
+
+    rays.iter()
+        .enumerate()
+        .flat_map(|(i, a)| rays[i + 1..].iter().map(move |b| (a, b)))
+        .filter(|(a, b)| crosses_within(a, b, (min, min), (max, max)))
+        .count()
+}
+
 fn main() {
-    println!("Hello, world!");
+    let input = input_str!(2023, 24);
+    let hailstones = time("Parse", || parse(input));
+
+    let part1 = time("Part 1", || {
+        part1(&hailstones, 200_000_000_000_000, 400_000_000_000_000)
+    });
+    println!("Part 1: {}", part1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_example() {
+        let hailstones = parse(EXAMPLE);
+        assert_eq!(part1(&hailstones, 7, 27), 2);
+    }
 }
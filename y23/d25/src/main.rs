@@ -1,3 +1,71 @@
+use aoc::{
+    graph::{component_sizes, global_min_cut, NamedGraph},
+    input_str,
+};
+
+/// Each line lists a component and the components it is wired to, wires are undirected.
+fn parse(input: &str) -> NamedGraph<'_> {
+    input
+        .lines()
+        .flat_map(|line| {
+            let (name, others) = line.split_once(": ").unwrap();
+            others.split_whitespace().map(move |other| (name, other))
+        })
+        .collect()
+}
+
+/// Cut the three wires that split the machine in two, and multiply the sizes of the two halves.
+pub fn part1(graph: &NamedGraph) -> usize {
+    let cut = global_min_cut(graph.adj()).unwrap();
+    assert_eq!(cut.weight, 3, "expected exactly three wires to cut");
+
+    component_sizes(graph.adj(), &cut.edges).iter().product()
+}
+
 fn main() {
-    println!("Hello, world!");
+    let input = input_str!(2023, 25);
+
+    let time = std::time::Instant::now();
+    let graph = parse(input);
+
+    println!("Part 1: {}", part1(&graph));
+    println!("Time: {:?}", time.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_example() {
+        let graph = parse(EXAMPLE);
+        assert_eq!(part1(&graph), 54);
+
+        // The wires to cut are hfx/pzl, bvb/cmg and nvd/jqt
+        let cut = global_min_cut(graph.adj()).unwrap();
+        let mut wires: Vec<(&str, &str)> = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let (a, b) = (graph.name(a), graph.name(b));
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        wires.sort();
+        assert_eq!(wires, vec![("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]);
+    }
 }
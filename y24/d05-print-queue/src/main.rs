@@ -1,117 +1,84 @@
-use std::cmp::Ordering;
-
-use aoc::input_str;
+use aoc::{
+    graph::Precedence,
+    input_str,
+    stringstuff::{ints_n, sections, uints},
+    time,
+};
 
 /// Eh, every problem is a graph problem.
 ///
-/// If (x, y) is in the matrix then X | Y, X comes before Y
-#[derive(Debug)]
-pub struct Graph {
-    n: usize,
-    matrix: Vec<bool>,
-}
+/// Each `X|Y` rule is an edge X -> Y, X comes before Y. The rules as a whole have cycles, but they
+/// are acyclic on every update.
+fn parse(input: &str) -> (Precedence<u32>, Vec<Vec<u32>>) {
+    let mut sections = sections(input);
+
+    let rules = sections
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let [x, y] = ints_n(line);
+            (x, y)
+        })
+        .collect();
 
-impl Graph {
-    pub fn new(n: usize) -> Self {
-        Self {
-            n,
-            matrix: vec![false; (n + 1) * (n + 1)],
-        }
-    }
+    let updates = sections
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| uints(line).collect())
+        .collect();
 
-    pub fn index(&self, x: usize, y: usize) -> usize {
-        y * self.n + x
-    }
+    (rules, updates)
+}
 
-    pub fn insert(&mut self, x: usize, y: usize) {
-        let idx = self.index(x, y);
-        self.matrix[idx] = true
-    }
+fn middle(pages: &[u32]) -> u32 {
+    pages[pages.len() / 2]
+}
 
-    pub fn contains(&self, x: usize, y: usize) -> bool {
-        let idx = self.index(x, y);
-        self.matrix[idx]
-    }
+fn part1(rules: &Precedence<u32>, updates: &[Vec<u32>]) -> u32 {
+    updates
+        .iter()
+        .filter(|pages| rules.is_ordered(pages))
+        .map(|pages| middle(pages))
+        .sum()
+}
+
+fn part2(rules: &Precedence<u32>, updates: &[Vec<u32>]) -> u32 {
+    updates
+        .iter()
+        .filter(|pages| !rules.is_ordered(pages))
+        .map(|pages| {
+            rules
+                .sort(pages)
+                .expect("rules should be acyclic on an update")
+        })
+        .map(|pages| middle(&pages))
+        .sum()
 }
 
 fn main() {
     let input = input_str!(2024, 5);
 
-    let start = std::time::Instant::now();
-
-    let mut lines = input.lines();
-
-    let mut counter = 0;
-    let mut mapping = [None; 100];
-    let mut reverse = vec![];
-    let mut constraints = vec![];
-
-    for line in lines.by_ref() {
-        if line.is_empty() {
-            break;
-        }
+    let (rules, updates) = time("Parse", || parse(input));
 
-        let mut split = line.split('|');
-        let x: usize = split.next().unwrap().parse().unwrap();
-        let y: usize = split.next().unwrap().parse().unwrap();
+    let part1 = time("Part 1", || part1(&rules, &updates));
+    println!("Part 1: {}", part1);
 
-        if mapping[x].is_none() {
-            counter += 1;
-            mapping[x] = Some(counter);
-            reverse.push(x);
-        }
+    let part2 = time("Part 2", || part2(&rules, &updates));
+    println!("Part 2: {}", part2);
+}
 
-        if mapping[y].is_none() {
-            counter += 1;
-            mapping[y] = Some(counter);
-            reverse.push(y);
-        }
+#[cfg(test)]
+mod test {
+    use crate::*;
 
-        constraints.push((mapping[x].unwrap(), mapping[y].unwrap()));
-    }
+    const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
 
-    debug_assert_eq!(mapping.iter().filter(|m| m.is_some()).count(), counter);
-    let mut graph = Graph::new(counter);
-    for (x, y) in constraints {
-        graph.insert(x, y);
+    #[test]
+    fn example() {
+        let (rules, updates) = parse(EXAMPLE);
+        assert_eq!(part1(&rules, &updates), 143);
+        assert_eq!(part2(&rules, &updates), 123);
     }
-
-    println!("Created graph: {:?}", start.elapsed());
-    let start = std::time::Instant::now();
-
-    let (good, bad): (Vec<_>, Vec<_>) = lines
-        .map(|line| {
-            line.split(',')
-                .map(|i| i.parse::<usize>().unwrap())
-                .map(|i| mapping[i].unwrap())
-                .collect::<Vec<_>>()
-        })
-        .partition(|pages| pages.windows(2).all(|v| graph.contains(v[0], v[1])));
-
-    println!("Partitioned: {:?}", start.elapsed());
-    let start = std::time::Instant::now();
-
-    let part1: usize = good
-        .into_iter()
-        .map(|pages| reverse[pages[pages.len() / 2] - 1])
-        .sum();
-
-    println!("Part 1: {:?}", start.elapsed());
-    println!("Part 1: {}", part1);
-    let start = std::time::Instant::now();
-
-    let part2: usize = bad
-        .into_iter()
-        .map(|mut page| {
-            page.sort_unstable_by(|x, y| match graph.contains(*x, *y) {
-                true => Ordering::Greater,
-                false => Ordering::Less,
-            });
-            page
-        })
-        .map(|pages| reverse[pages[pages.len() / 2] - 1])
-        .sum();
-
-    println!("Part 2: {:?}", start.elapsed());
-    println!("Part 2: {}", part2);
 }
@@ -1,4 +1,4 @@
-use aoc::{input_str, time};
+use aoc::{input_str, linalg::Matrix, scan, time};
 
 // Button A: X+94, Y+34
 // Button B: X+22, Y+67
@@ -11,67 +11,37 @@ use aoc::{input_str, time};
 #[derive(Debug, Clone, Copy)]
 struct Problem {
     // a b c d
-    matrix: [isize; 4],
-    x: isize,
-    y: isize,
+    matrix: [i64; 4],
+    x: i64,
+    y: i64,
 }
 
 impl Problem {
-    fn solve_p1(&self) -> Option<(usize, usize)> {
-        println!();
-        let det = self.matrix[0] * self.matrix[3] - self.matrix[1] * self.matrix[2];
-        println!("{}", det);
-        if det == 0 {
-            println!("Determinant is 0");
-            return None;
-        }
-
-        let inv = [
-            self.matrix[3],
-            -self.matrix[1],
-            -self.matrix[2],
-            self.matrix[0],
-        ];
-
-        println!("{:?}", inv);
-
-        let a = self.x * inv[0] + self.y * inv[1];
-        let b = self.x * inv[2] + self.y * inv[3];
-
-        println!("a: {}, b: {}", a, b);
-        println!("a: {}, b: {}", a / det, b / det);
-        println!("a: {}, b: {}", a % det, b % det);
-
-        // 17990 - low
-        if a % det == 0 && b % det == 0 {
-            Some(((a / det) as usize, (b / det) as usize))
-        } else {
-            None
+    /// Returns the number of (A, B) presses that reach the prize, if it can be reached
+    fn solve(&self) -> Option<(i64, i64)> {
+        let [a, b, c, d] = self.matrix;
+        let presses = Matrix::from_rows([[a, b], [c, d]])
+            .solve([self.x, self.y])
+            .integer()?;
+
+        match presses[..] {
+            [a, b] if a >= 0 && b >= 0 => Some((a, b)),
+            _ => None,
         }
     }
 }
 
 fn parse(input: &str) -> Vec<Problem> {
-    let re = regex::Regex::new(r"(\d+)").unwrap();
     let lines: Vec<_> = input.lines().collect();
     lines
         .chunks(4)
         .map(|l| {
-            let cap = re.captures_iter(l[0]).collect::<Vec<_>>();
-
-            let a = cap[0].get(0).unwrap().as_str().parse().unwrap();
-            let b = cap[1].get(1).unwrap().as_str().parse().unwrap();
-
-            let cap = re.captures_iter(l[1]).collect::<Vec<_>>();
-            let c = cap[0].get(0).unwrap().as_str().parse().unwrap();
-            let d = cap[1].get(1).unwrap().as_str().parse().unwrap();
-
-            let cap = re.captures_iter(l[2]).collect::<Vec<_>>();
-            let x = cap[0].get(0).unwrap().as_str().parse().unwrap();
-            let y = cap[1].get(1).unwrap().as_str().parse().unwrap();
+            let (a, c) = scan!(l[0], "Button A: X+{}, Y+{}" => i64, i64).unwrap();
+            let (b, d) = scan!(l[1], "Button B: X+{}, Y+{}" => i64, i64).unwrap();
+            let (x, y) = scan!(l[2], "Prize: X={}, Y={}" => i64, i64).unwrap();
 
             Problem {
-                matrix: [a, c, b, d],
+                matrix: [a, b, c, d],
                 x,
                 y,
             }
@@ -79,15 +49,15 @@ fn parse(input: &str) -> Vec<Problem> {
         .collect()
 }
 
-fn part1(problems: &[Problem]) -> usize {
+fn part1(problems: &[Problem]) -> i64 {
     problems
         .iter()
-        .filter_map(|p| p.solve_p1())
+        .filter_map(|p| p.solve())
         .map(|(a, b)| 3 * a + b)
         .sum()
 }
 
-fn part2(problems: &[Problem]) -> usize {
+fn part2(problems: &[Problem]) -> i64 {
     problems
         .iter()
         .map(|p| Problem {
@@ -95,7 +65,7 @@ fn part2(problems: &[Problem]) -> usize {
             x: p.x + 10000000000000,
             y: p.y + 10000000000000,
         })
-        .filter_map(|p| p.solve_p1())
+        .filter_map(|p| p.solve())
         .map(|(a, b)| 3 * a + b)
         .sum()
 }
@@ -106,5 +76,35 @@ fn main() {
     let part1 = time("Part 1", || part1(&problems));
     println!("Part 1: {}", part1);
     let part2 = time("Part 2", || part2(&problems));
-    println!("Part 1: {}", part2);
+    println!("Part 2: {}", part2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn test_example() {
+        let problems = parse(EXAMPLE);
+        assert_eq!(problems[0].solve(), Some((80, 40)));
+        assert_eq!(problems[1].solve(), None);
+        assert_eq!(part1(&problems), 480);
+        assert_eq!(part2(&problems), 875318608908);
+    }
 }
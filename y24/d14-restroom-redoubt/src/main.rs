@@ -1,41 +1,21 @@
-use std::i32;
-
-use aoc::{input_str, time};
+use aoc::{input_str, math, scan, time};
 
 const WIDTH: u32 = 101;
 const HEIGHT: u32 = 103;
 
-fn parse_input(input: &str) -> ((u32, u32), (u32, u32)) {
-    // Split the input by spaces to separate "p=" and "v="
-    let parts: Vec<&str> = input.split_whitespace().collect();
-
-    // Extract and parse the "p=" part
-    let p_str = parts[0].trim_start_matches("p=");
-    let p: (u32, u32) = {
-        let coords: Vec<u32> = p_str.split(',').map(|s| s.parse().unwrap()).collect();
-        (coords[0] % WIDTH, coords[1] % HEIGHT)
-    };
-
-    // Extract and parse the "v=" part
-    let v_str = parts[1].trim_start_matches("v=");
-    let v: (u32, u32) = {
-        let coords: Vec<i32> = v_str.split(',').map(|s| s.parse().unwrap()).collect();
-        // coords are itialized as signed integers, but we need to make them positive
-        // so we convert them to unsigned integers by finding the modular inverse
-        let vx = if coords[0] < 0 {
-            (WIDTH as i32 + coords[0]) as u32
-        } else {
-            coords[0] as u32
-        };
-
-        let vy = if coords[1] < 0 {
-            (HEIGHT as i32 + coords[1]) as u32
-        } else {
-            coords[1] as u32
-        };
-
-        (vx, vy)
-    };
+/// Position and velocity, both wrapped into the room
+type Robot = ((u32, u32), (u32, u32));
+
+// p=0,4 v=3,-3
+fn parse_input(input: &str) -> Robot {
+    let (px, py, vx, vy) = scan!(input, "p={},{} v={},{}" => u32, u32, i32, i32).unwrap();
+
+    // velocities are signed, but moving by -v is the same as moving by WIDTH - v
+    let p = (px % WIDTH, py % HEIGHT);
+    let v = (
+        math::modulo(vx, WIDTH as i32) as u32,
+        math::modulo(vy, HEIGHT as i32) as u32,
+    );
     (p, v)
 }
 
@@ -65,7 +45,7 @@ fn quadrant(x: u32, y: u32) -> Option<i32> {
 fn part1(input: &str) -> usize {
     let (q1, q2, q3, q4) = input
         .lines()
-        .map(|l| parse_input(l))
+        .map(parse_input)
         .map(|(p, v)| simulate_p1(p, v))
         .filter_map(|p| quadrant(p.0, p.1))
         .fold((0, 0, 0, 0), |(q1, q2, q3, q4), quad| match quad {
@@ -83,8 +63,8 @@ fn step((x, y): (u32, u32), (vx, vy): (u32, u32)) -> (u32, u32) {
     ((x + vx) % WIDTH, (y + vy) % HEIGHT)
 }
 
-fn part2(input: &str) -> (usize, Vec<((u32, u32), (u32, u32))>) {
-    let mut robots: Vec<_> = input.lines().map(|l| parse_input(l)).collect();
+fn part2(input: &str) -> (usize, Vec<Robot>) {
+    let mut robots: Vec<_> = input.lines().map(parse_input).collect();
     let mut min_robots = vec![];
     let mut min_score = i32::MAX;
     let mut best_i = 0;
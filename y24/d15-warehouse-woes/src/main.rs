@@ -1,9 +1,15 @@
 use std::collections::HashMap;
 
-use aoc::input_str;
+use aoc::{input_str, stringstuff::sections};
+
+/// The warehouse map and the robot's moves
+fn split_sections(input: &str) -> [&str; 2] {
+    let mut sections = sections(input);
+    [sections.next().unwrap(), sections.next().unwrap()]
+}
 
 fn part1(input: &str) -> i32 {
-    let (map, instructions) = input.split_once("\n\n").unwrap();
+    let [map, instructions] = split_sections(input);
 
     let mut robot = None;
     let mut game = HashMap::new();
@@ -39,7 +45,7 @@ fn part1(input: &str) -> i32 {
 }
 
 fn part2(input: &str) -> i32 {
-    let (map, instructions) = input.split_once("\n\n").unwrap();
+    let [map, instructions] = split_sections(input);
 
     let mut robot = None;
     let mut game = HashMap::new();
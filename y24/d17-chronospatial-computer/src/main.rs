@@ -1,4 +1,10 @@
-use aoc::input_str;
+use aoc::{
+    error, input_str,
+    parse::{self, Parser},
+};
+
+// The instructions whose operand is a combo operand: adv, bst, out, bdv and cdv
+const COMBO: [u64; 5] = [0, 2, 5, 6, 7];
 
 #[derive(Debug, Clone)]
 struct Computer {
@@ -28,7 +34,7 @@ impl Computer {
             4 => self.regs[0],
             5 => self.regs[1],
             6 => self.regs[2],
-            _ => panic!("Invalid operand"),
+            _ => unreachable!("combo operand 7 is rejected by parse_input"),
         }
     }
 
@@ -82,75 +88,87 @@ impl Computer {
     }
 }
 
-fn main() {
-    let input_str = input_str!(2024, 17);
-
-    // Parse the input
-    let lines: Vec<&str> = input_str.trim().lines().collect();
-
-    // Parse register A
-    let reg_a = lines[0]
-        .strip_prefix("Register A: ")
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-
-    // Parse register B
-    let reg_b = lines[1]
-        .strip_prefix("Register B: ")
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-
-    // Parse register C
-    let reg_c = lines[2]
-        .strip_prefix("Register C: ")
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-
-    // Parse program
-    let program: Vec<u64> = lines[4]
-        .strip_prefix("Program: ")
-        .unwrap()
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect();
-
-    let time = std::time::Instant::now();
-    let mut computer = Computer::new(reg_a, reg_b, reg_c, program.clone());
-    computer.run();
-
-    println!(
-        "Part 1: {}",
-        computer
-            .output
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join(",")
-    );
-    println!("Time: {:?}", time.elapsed());
-
-    let time = std::time::Instant::now();
-    let mut a = 0;
-    for i in (0..program.len()).rev() {
-        a <<= 3;
-
-        loop {
-            let mut computer = Computer::new(a, 0, 0, program.clone());
-            computer.run();
-
-            // check if we've got a partial solution
-            if computer.output[..] == program[i..] {
-                break;
+// Register A: 729
+// Register B: 0
+// Register C: 0
+//
+// Program: 0,1,5,4,3,0
+fn parse_input(input: &str) -> parse::Result<([u64; 3], Vec<u64>)> {
+    parse::parse(input, |p: &mut Parser| {
+        let mut regs = [0; 3];
+        for (reg, name) in regs.iter_mut().zip(["A", "B", "C"]) {
+            p.literal("Register ")?;
+            p.literal(name)?;
+            p.literal(": ")?;
+            *reg = p.uint()?;
+            p.newline()?;
+        }
+        p.newline()?;
+        p.literal("Program: ")?;
+        // Instructions alternate with their operands, and operands are checked against the
+        // instruction before them so that the computer never sees an invalid one
+        let mut instruction = None;
+        let program = p.separated(",", |p| match instruction.take() {
+            None => {
+                let opcode = p.verify(|p| p.uint(), |&n| n < 8, "opcode from 0 to 7")?;
+                instruction = Some(opcode);
+                Ok(opcode)
+            }
+            Some(opcode) if COMBO.contains(&opcode) => {
+                p.verify(|p| p.uint(), |&n| n < 7, "combo operand from 0 to 6")
             }
+            Some(_) => p.verify(|p| p.uint(), |&n| n < 8, "operand from 0 to 7"),
+        })?;
+        if program.len() % 2 != 0 {
+            return Err(p.error("operand"));
+        }
+        Ok((regs, program))
+    })
+}
+
+fn main() {
+    error::run(|| {
+        let input_str = input_str!(2024, 17);
+
+        let (regs, program) = parse_input(input_str)?;
+        let [reg_a, reg_b, reg_c] = regs;
+
+        let time = std::time::Instant::now();
+        let mut computer = Computer::new(reg_a, reg_b, reg_c, program.clone());
+        computer.run();
+
+        println!(
+            "Part 1: {}",
+            computer
+                .output
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        println!("Time: {:?}", time.elapsed());
+
+        let time = std::time::Instant::now();
+        let mut a = 0;
+        for i in (0..program.len()).rev() {
+            a <<= 3;
+
+            loop {
+                let mut computer = Computer::new(a, 0, 0, program.clone());
+                computer.run();
+
+                // check if we've got a partial solution
+                if computer.output[..] == program[i..] {
+                    break;
+                }
 
-            a += 1;
+                a += 1;
+            }
         }
-    }
-    println!("Part 2: {}", a);
-    println!("Time: {:?}", time.elapsed());
+        println!("Part 2: {}", a);
+        println!("Time: {:?}", time.elapsed());
+        Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -200,8 +218,20 @@ mod tests {
 
     #[test]
     fn test_example() {
-        let mut computer = Computer::new(729, 0, 0, vec![0, 1, 5, 4, 3, 0]);
+        let input = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n";
+        let ([a, b, c], program) = parse_input(input).unwrap();
+        let mut computer = Computer::new(a, b, c, program);
         while computer.step() {}
         assert_eq!(computer.output, vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
     }
+
+    #[test]
+    fn test_invalid_operand() {
+        let input = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,7,3,0\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 16: expected combo operand from 0 to 6"
+        );
+    }
 }
@@ -1,69 +1,29 @@
-use aoc::input_str;
+use std::ops::RangeInclusive;
 
-// A number is 'repeating' if it is form like 55, 123123, etc
-fn part1_is_repeating(n: i64) -> bool {
-    // log base 10 tells us the number of digits
-    let digits = (n as f64).log10().floor() as u32 + 1;
-
-    // get half the digits
-    let top = n / 10_i64.pow(digits / 2);
-    let bottom = n % 10_i64.pow(digits / 2);
+use aoc::{digits::repeated_blocks, input_str};
 
-    top == bottom
+fn parse_ranges(input: &str) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+    input.trim().split(',').map(|s| {
+        let (start, end) = s.split_once('-').unwrap();
+        start.parse().unwrap()..=end.parse().unwrap()
+    })
 }
 
-fn part1(input: &str) -> i64 {
-    input
-        .trim()
-        .split(',')
-        .flat_map(|s| {
-            let parts = s.split('-').collect::<Vec<&str>>();
-            let start = parts[0].parse().unwrap();
-            let end = parts[1].parse().unwrap();
-            start..=end
-        })
-        .filter(|&n| part1_is_repeating(n))
+/// Sums the numbers made of a digit block repeated a number of times in `repeats`
+fn sum_repeating(input: &str, repeats: RangeInclusive<u32>) -> i64 {
+    parse_ranges(input)
+        .flat_map(|range| repeated_blocks(range, repeats.clone(), 10))
         .sum()
 }
 
-// now numbers with multiple repeats count (12341234, 123123123, 1212121212)
-fn part2_is_repeating(digits: &mut Vec<i64>, mut n: i64) -> bool {
-    digits.clear();
-
-    // convert the number to a vec of digits
-    while n > 0 {
-        digits.push(n % 10);
-        n /= 10;
-    }
-
-    // check for sequences of length 1, 2, 3 .. n/2
-    for chunk_size in 1..=digits.len() / 2 {
-        // verify the chunk size factors into len
-        if digits.len().is_multiple_of(chunk_size) {
-            let mut chunks = digits.chunks(chunk_size);
-            let first = chunks.next().unwrap();
-            if chunks.all(|w| w == first) {
-                return true;
-            }
-        }
-    }
-    false
+// A number is 'repeating' if it is form like 55, 123123, etc
+fn part1(input: &str) -> i64 {
+    sum_repeating(input, 2..=2)
 }
 
+// now numbers with multiple repeats count (12341234, 123123123, 1212121212)
 fn part2(input: &str) -> i64 {
-    let mut buffer = Vec::new();
-
-    input
-        .trim()
-        .split(',')
-        .flat_map(|s| {
-            let parts = s.split('-').collect::<Vec<&str>>();
-            let start = parts[0].parse().unwrap();
-            let end = parts[1].parse().unwrap();
-            start..=end
-        })
-        .filter(|&n| part2_is_repeating(&mut buffer, n))
-        .sum()
+    sum_repeating(input, 2..=u32::MAX)
 }
 
 fn main() {
@@ -83,20 +43,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_is_repeating() {
-        assert!(part1_is_repeating(55));
-        assert!(part1_is_repeating(123123));
-        assert!(!part1_is_repeating(123456));
-
-        let mut buffer = Vec::new();
-        assert!(part2_is_repeating(&mut buffer, 12341234));
-        assert!(part2_is_repeating(&mut buffer, 123123123));
-        assert!(part2_is_repeating(&mut buffer, 1212121212));
-    }
-
-    #[test]
-    fn test_part1() {
+    fn test_example() {
         let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
         assert_eq!(part1(input), 1227775554);
+        assert_eq!(part2(input), 4174379265);
     }
 }
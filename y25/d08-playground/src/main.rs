@@ -1,14 +1,15 @@
-use std::collections::HashMap;
+use aoc::{KeyedUnionFind, input_str};
 
-use aoc::{IterJunk, UnionFind, input_str};
+type Point = (i64, i64, i64);
 
-fn dist(a: &(i64, i64, i64), b: &(i64, i64, i64)) -> i64 {
+fn dist(a: &Point, b: &Point) -> i64 {
     (a.0 - b.0) * (a.0 - b.0) + (a.1 - b.1) * (a.1 - b.1) + (a.2 - b.2) * (a.2 - b.2)
 }
 
-fn part1(input: &str, n: usize) -> usize {
+/// Parses the junction boxes, each on its own set, and every pair of them from closest to farthest.
+fn parse(input: &str) -> (KeyedUnionFind<Point>, Vec<(Point, Point)>) {
     // x,y,z
-    let points: Vec<(i64, i64, i64)> = input
+    let points: Vec<Point> = input
         .lines()
         .map(|line| {
             let mut parts = line.split(',');
@@ -19,73 +20,47 @@ fn part1(input: &str, n: usize) -> usize {
         })
         .collect::<Vec<_>>();
 
+    let mut circuits = KeyedUnionFind::new();
+    for &point in &points {
+        circuits.insert(point);
+    }
+
     // calculate the distance between every pair of points
-    let mut distances: Vec<(i64, (usize, usize))> = Vec::new();
+    let mut distances: Vec<(i64, (Point, Point))> = Vec::new();
     for i in 0..points.len() {
         for j in i + 1..points.len() {
-            let dist = dist(&points[i], &points[j]);
-            distances.push((dist, (i, j)));
+            distances.push((dist(&points[i], &points[j]), (points[i], points[j])));
         }
     }
 
     // sort the distances in ascending order
     distances.sort_unstable();
 
-    let mut disjoint_set = UnionFind::new(points.len());
+    (
+        circuits,
+        distances.into_iter().map(|(_, pair)| pair).collect(),
+    )
+}
 
-    // join the 1000 closest points
-    distances.iter().take(n).for_each(|&(_, (i, j))| {
-        disjoint_set.union(i, j);
-    });
+fn part1(input: &str, n: usize) -> usize {
+    let (mut circuits, pairs) = parse(input);
 
-    // find the 3 largest groups
-    let mut groups = HashMap::new();
-    for i in 0..disjoint_set.len() {
-        let group = disjoint_set.find(i);
-        *groups.entry(group).or_insert(0) += 1;
+    // join the n closest points
+    for &(a, b) in pairs.iter().take(n) {
+        circuits.union(a, b);
     }
 
-    let groups: Vec<_> = groups.into_iter().collect();
-    groups
-        .iter()
-        .k_largest_by_key(3, |&(_, count)| count)
-        .map(|(_, count)| count)
-        .product()
+    // multiply the sizes of the 3 largest circuits
+    circuits.component_sizes().iter().take(3).product()
 }
 
 fn part2(input: &str) -> Option<i64> {
-    // x,y,z
-    let points: Vec<(i64, i64, i64)> = input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split(',');
-            let x = parts.next().unwrap().parse().unwrap();
-            let y = parts.next().unwrap().parse().unwrap();
-            let z = parts.next().unwrap().parse().unwrap();
-            (x, y, z)
-        })
-        .collect::<Vec<_>>();
-
-    // calculate the distance between every pair of points
-    let mut distances: Vec<(i64, (usize, usize))> = Vec::new();
-    for i in 0..points.len() {
-        for j in i + 1..points.len() {
-            let dist = dist(&points[i], &points[j]);
-            distances.push((dist, (i, j)));
-        }
-    }
-
-    // sort the distances in ascending order
-    distances.sort_unstable();
-
-    let mut disjoint_set = UnionFind::new(points.len());
+    let (mut circuits, pairs) = parse(input);
 
     // continue until all points are in the same group
-    for (_, (i, j)) in distances {
-        disjoint_set.union(i, j);
-        if disjoint_set.num_groups() == 1 {
-            let a = points[i];
-            let b = points[j];
+    for (a, b) in pairs {
+        circuits.union(a, b);
+        if circuits.num_groups() == 1 {
             return Some(a.0 * b.0);
         }
     }
@@ -114,4 +89,10 @@ mod tests {
         let example = "162,817,812\n57,618,57\n906,360,560\n592,479,940\n352,342,300\n466,668,158\n542,29,236\n431,825,988\n739,650,466\n52,470,668\n216,146,977\n819,987,18\n117,168,530\n805,96,715\n346,949,466\n970,615,88\n941,993,340\n862,61,35\n984,92,344\n425,690,689";
         assert_eq!(part1(example, 10), 40);
     }
+
+    #[test]
+    fn test_part2() {
+        let example = "162,817,812\n57,618,57\n906,360,560\n592,479,940\n352,342,300\n466,668,158\n542,29,236\n431,825,988\n739,650,466\n52,470,668\n216,146,977\n819,987,18\n117,168,530\n805,96,715\n346,949,466\n970,615,88\n941,993,340\n862,61,35\n984,92,344\n425,690,689";
+        assert_eq!(part2(example), Some(25272));
+    }
 }